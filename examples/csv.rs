@@ -0,0 +1,26 @@
+use planetensimulation::{Board, CsvWriter, ExtinctionPolicy};
+use std::io::Write;
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    env_logger::builder()
+        .format(|buf, record| writeln!(buf, "{}: {}", record.level(), record.args()))
+        .init();
+
+    let mut board =
+        Board::new(200, 100, 40, 40).with_extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+    board.generate_random_animals();
+
+    let mut writer = CsvWriter::new(std::io::stdout().lock()).unwrap();
+    writer
+        .write_report(board.statistics().last().unwrap())
+        .unwrap();
+
+    for _ in 0..ITERATIONS {
+        match board.step() {
+            Ok(report) => writer.write_report(&report).unwrap(),
+            Err(_) => break,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use planetensimulation::Board;
+use planetensimulation::{Board, ExtinctionPolicy};
 use std::io::Write;
 
 fn main() {
@@ -6,13 +6,11 @@ fn main() {
         .format(|buf, record| writeln!(buf, "{}: {}", record.level(), record.args()))
         .init();
 
-    let mut board = Board::new(4, 2, 5, 5);
+    let mut board =
+        Board::new(4, 2, 5, 5).with_extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
     board.generate_random_animals();
 
-    loop {
-        if let Err(_) = board.step() {
-            break;
-        }
+    while board.step().is_ok() {
         println!("{}", board);
         std::thread::sleep(std::time::Duration::from_millis(500));
     }
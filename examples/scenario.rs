@@ -0,0 +1,19 @@
+use planetensimulation::Board;
+use std::{env, fs::File};
+
+/// Loads a scenario file and prints every step
+///
+/// Run with `cargo run --example scenario -- examples/scenarios/surrounded_shark.txt`
+fn main() -> planetensimulation::Result {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/scenarios/surrounded_shark.txt".into());
+    let mut board = Board::from_reader(File::open(path)?)?;
+    println!("{}", board);
+
+    for _ in 0..10 {
+        board.step()?;
+        println!("{}", board);
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use planetensimulation::{Board, ExtinctionPolicy, Sweep};
+
+const SUMMARY_FILENAME: &str = "sweep.csv";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = Board::builder()
+        .dimensions(40, 40)
+        .extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+
+    // 3 x 3 x 2 combinations with 10 seeds each, simulated on all CPU cores
+    let table = Sweep::new(builder)
+        .fish_breed_times(2..=4)
+        .shark_breed_times([6, 8, 10])
+        .fish_densities([0.125, 0.25])
+        .shark_densities([0.0625])
+        .repeats(10)
+        .steps(500)
+        .seed(42)
+        .run()?;
+
+    table.write_summary_csv(std::fs::File::create(SUMMARY_FILENAME)?)?;
+    println!("{SUMMARY_FILENAME} written to current directory");
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use planetensimulation::{Board, Viewer};
+use std::{env, fs};
+
+/// Shows a random board or a scenario file in the terminal viewer
+///
+/// Run with `cargo run --example tui --features tui -- [scenario file]`
+fn main() -> planetensimulation::Result {
+    let scenario = env::args().nth(1).map(fs::read_to_string).transpose()?;
+
+    Viewer::new(|seed| match &scenario {
+        Some(text) => text.parse::<Board>().unwrap().with_seed(seed),
+        None => Board::builder()
+            .dimensions(40, 40)
+            .fishes(200)
+            .sharks(100)
+            .seed(seed)
+            .build()
+            .unwrap(),
+    })
+    .run()?;
+    Ok(())
+}
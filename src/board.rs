@@ -9,27 +9,61 @@
 //! board.generate_random_animals();
 //! //board.draw();
 //! ```
+//!
+//! A board that is created with a seed always produces the same simulation:
+//! ```
+//! use planetensimulation::Board;
+//!
+//! let mut first = Board::new(10, 5, 25, 25).with_seed(42);
+//! let mut second = Board::new(10, 5, 25, 25).with_seed(42);
+//! first.generate_random_animals();
+//! second.generate_random_animals();
+//! assert_eq!(first, second);
+//! ```
 use log::debug;
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
-    field::{Field, FieldType},
-    Result, SimulationError,
+    field::{AnimalStatus, Field, FieldType},
+    BoardBuilder, ExtinctionPolicy, Lineage, Neighbourhood, Result, SimulationConfig,
+    SimulationError, SimulationEvent, Statistics, StepReport, Topology, UpdateOrder,
 };
 
+type Position = (u32, u32);
+
 /// Holds all the fields and information of the simulation
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
     amount_fishes: u32,
     amount_sharks: u32,
     rows: u32,
     columns: u32,
     fields: Vec<Vec<Field>>,
+    config: SimulationConfig,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    update_order: UpdateOrder,
+    extinction_policy: ExtinctionPolicy,
+    rng: ChaCha8Rng,
+    current_step: u32,
+    next_id: u64,
+    statistics: Statistics,
+    lineage: Option<Lineage>,
 }
 
 impl Board {
+    /// Creates a builder for a board
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder::default()
+    }
+
     /// Creates a new board
     ///
+    /// Use [`Board::builder`] to get an error instead of a panic for invalid parameters.
+    ///
     /// # Arguments
     /// * `rows` - The amount of rows
     /// * `columns` - The amount of columns
@@ -37,10 +71,14 @@ impl Board {
     /// * `amount_sharks` - The amount of sharks that are placed on the board initially
     ///
     /// # Panics
-    /// If the amount of fishes and sharks is greater than the amount of fields
+    /// * If the amount of fields does not fit into a `u32`
+    /// * If the amount of fishes and sharks is greater than the amount of fields
     pub fn new(amount_fishes: u32, amount_sharks: u32, rows: u32, columns: u32) -> Self {
+        let Some(fields) = rows.checked_mul(columns) else {
+            panic!("The amount of fields does not fit into a u32");
+        };
         // If the amount of fishes and sharks is bigger than the amount of fields panic
-        if amount_fishes + amount_sharks > rows * columns {
+        if u64::from(amount_fishes) + u64::from(amount_sharks) > u64::from(fields) {
             panic!("The amount of fishes and sharks is bigger than the amount of fields");
         }
 
@@ -49,111 +87,754 @@ impl Board {
             amount_sharks,
             rows,
             columns,
-            fields: Vec::with_capacity((amount_fishes + amount_sharks) as usize),
+            fields: Self::empty_fields(rows, columns),
+            config: SimulationConfig::default(),
+            topology: Topology::default(),
+            neighbourhood: Neighbourhood::default(),
+            update_order: UpdateOrder::default(),
+            extinction_policy: ExtinctionPolicy::default(),
+            rng: ChaCha8Rng::from_entropy(),
+            current_step: 0,
+            next_id: 1,
+            statistics: Statistics::default(),
+            lineage: None,
         }
     }
 
-    /// Generate a new board with the amount of fishes and sharks
-    pub fn generate_random_animals(&mut self) {
-        let mut rand_gen = rand::thread_rng();
+    /// Sets the parameters of the simulation
+    ///
+    /// # Arguments
+    /// * `config` - The parameters that are used for every animal on the board
+    ///
+    /// # Errors
+    /// [`SimulationError::InvalidConfig`] if a parameter is invalid (see
+    /// [`SimulationConfig::validate`])
+    pub fn with_config(mut self, config: SimulationConfig) -> Result<Self> {
+        config.validate()?;
+        self.config = config;
+        Ok(self)
+    }
 
-        // Initialize an empty 2d vector
-        let mut animals: Vec<Vec<Field>> = Vec::with_capacity(self.rows as usize);
-        for y in 0..self.rows {
-            let mut animals_row = Vec::with_capacity(self.columns as usize);
-            for x in 0..self.columns {
-                animals_row.push(Field::new(FieldType::Plankton, x, y, None));
+    /// Returns the parameters of the simulation
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Sets which edges of the board wrap around
+    ///
+    /// # Arguments
+    /// * `topology` - The topology that is used to look up the neighbours of an animal
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Returns which edges of the board wrap around
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Sets which fields around an animal it can move to or hunt on
+    ///
+    /// # Arguments
+    /// * `neighbourhood` - The neighbourhood that is used for fishes and sharks
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Returns which fields around an animal it can move to or hunt on
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// Sets the order in which the animals act during a step
+    ///
+    /// # Arguments
+    /// * `update_order` - The order that is used for every step
+    pub fn with_update_order(mut self, update_order: UpdateOrder) -> Self {
+        self.update_order = update_order;
+        self
+    }
+
+    /// Returns the order in which the animals act during a step
+    pub fn update_order(&self) -> UpdateOrder {
+        self.update_order
+    }
+
+    /// Sets whether the simulation stops when a species died out
+    ///
+    /// By default the surviving population keeps being simulated.
+    ///
+    /// # Arguments
+    /// * `policy` - The policy that is checked before every step
+    pub fn with_extinction_policy(mut self, policy: ExtinctionPolicy) -> Self {
+        self.extinction_policy = policy;
+        self
+    }
+
+    /// Logs the birth and death of every animal
+    ///
+    /// The animals that are already on the board are logged as if they were born in the current
+    /// step.
+    pub fn with_lineage_log(mut self) -> Self {
+        let mut lineage = Lineage::default();
+        for field in self.fields.iter().flatten() {
+            if let Some(status) = &field.status {
+                lineage.record_birth(
+                    status.id(),
+                    status.parent_id(),
+                    field.r#type.clone(),
+                    self.current_step,
+                );
             }
-            animals.push(animals_row)
         }
+        self.lineage = Some(lineage);
+        self
+    }
+
+    /// Returns the log of births and deaths or `None` if it was not enabled with
+    /// [`Board::with_lineage_log`]
+    pub fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    /// Seeds the random number generator of the board
+    ///
+    /// Every random decision of the simulation (placement of the animals, initial life of the
+    /// sharks and every move) is drawn from this generator. Two boards with the same seed and
+    /// parameters therefore produce identical simulations.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed for the random number generator
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Generate a new board with the amount of fishes and sharks
+    pub fn generate_random_animals(&mut self) {
+        // Initialize an empty 2d vector
+        let mut animals = Self::empty_fields(self.rows, self.columns);
 
         // Randomly insert fishes into the empty field
         for _ in 0..self.amount_fishes {
-            let mut random_x = rand_gen.gen_range(0..animals.first().unwrap().len());
-            let mut random_y = rand_gen.gen_range(0..animals.len());
+            let mut random_x = self.rng.gen_range(0..animals.first().unwrap().len());
+            let mut random_y = self.rng.gen_range(0..animals.len());
 
             while animals[random_y][random_x].r#type != FieldType::Plankton {
-                random_x = rand_gen.gen_range(0..animals.first().unwrap().len());
-                random_y = rand_gen.gen_range(0..animals.len());
+                random_x = self.rng.gen_range(0..animals.first().unwrap().len());
+                random_y = self.rng.gen_range(0..animals.len());
             }
             animals[random_y][random_x] =
-                Field::new(FieldType::Fish, random_x as u32, random_y as u32, None);
+                self.spawn(FieldType::Fish, random_x as u32, random_y as u32);
         }
         // Randomly insert sharks into the empty field
         for _ in 0..self.amount_sharks {
-            let mut random_col = rand_gen.gen_range(0..animals.first().unwrap().len());
-            let mut random_row = rand_gen.gen_range(0..animals.len());
+            let mut random_col = self.rng.gen_range(0..animals.first().unwrap().len());
+            let mut random_row = self.rng.gen_range(0..animals.len());
 
             while animals[random_row][random_col].r#type != FieldType::Plankton {
-                random_col = rand_gen.gen_range(0..animals.first().unwrap().len());
-                random_row = rand_gen.gen_range(0..animals.len());
+                random_col = self.rng.gen_range(0..animals.first().unwrap().len());
+                random_row = self.rng.gen_range(0..animals.len());
             }
 
             animals[random_row][random_col] =
-                Field::new(FieldType::Shark, random_col as u32, random_row as u32, None);
+                self.spawn(FieldType::Shark, random_col as u32, random_row as u32);
         }
 
         self.fields = animals;
+        self.record_initial_state();
+        debug!("Initial state:\n{}", self);
+    }
+
+    /// Replaces all fields with plankton and places animals with a fresh status on the given
+    /// positions
+    ///
+    /// # Arguments
+    /// * `animals` - The positions and types of the animals
+    pub(crate) fn place_animals(&mut self, animals: &[(Position, FieldType)]) {
+        let mut fields = Self::empty_fields(self.rows, self.columns);
+        for ((x, y), r#type) in animals {
+            fields[*y as usize][*x as usize] = self.spawn(r#type.clone(), *x, *y);
+        }
+
+        self.fields = fields;
+        (self.amount_fishes, self.amount_sharks) = self.count_animals();
+        self.record_initial_state();
         debug!("Initial state:\n{}", self);
     }
 
+    /// Returns the amount of rows of the board
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Returns the amount of columns of the board
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// Returns the number of the last simulated step or `0` if no step was simulated yet
+    pub fn current_step(&self) -> u32 {
+        self.current_step
+    }
+
+    /// Returns all fields of the board row by row
+    pub fn cells(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().flatten()
+    }
+
+    /// Returns the position and status of every fish row by row
+    pub fn fishes(&self) -> impl Iterator<Item = (Position, &AnimalStatus)> {
+        self.animals_of(FieldType::Fish)
+    }
+
+    /// Returns the position and status of every shark row by row
+    pub fn sharks(&self) -> impl Iterator<Item = (Position, &AnimalStatus)> {
+        self.animals_of(FieldType::Shark)
+    }
+
+    fn animals_of(&self, r#type: FieldType) -> impl Iterator<Item = (Position, &AnimalStatus)> {
+        self.cells()
+            .filter(move |field| field.r#type == r#type)
+            .filter_map(|field| Some(((field.x, field.y), field.status.as_ref()?)))
+    }
+
+    /// Returns the field at a position
+    ///
+    /// # Arguments
+    /// * `x` - The column of the field
+    /// * `y` - The row of the field
+    ///
+    /// # Returns
+    /// The field or `None` if the position is outside of the board
+    pub fn get(&self, x: u32, y: u32) -> Option<&Field> {
+        self.fields.get(y as usize)?.get(x as usize)
+    }
+
+    /// Puts something on a field and replaces whatever was there before
+    ///
+    /// A replaced animal is logged as dead in the lineage log, but it is not added to the
+    /// lifespans of the statistics because it was neither eaten nor starved. If no steps were
+    /// simulated yet the initial state in the statistics is updated.
+    ///
+    /// # Arguments
+    /// * `x` - The column of the field
+    /// * `y` - The row of the field
+    /// * `type` - What is put on the field
+    /// * `status` - The status of the animal or `None` for a freshly born animal. The animal
+    ///   keeps its age, life and breed counter but always gets a new id, so an animal that is
+    ///   moved with [`Board::clear`] and `place` is logged as a new animal.
+    ///
+    /// # Returns
+    /// The field that was replaced
+    ///
+    /// # Errors
+    /// * [`SimulationError::OutOfBounds`] if the position is outside of the board
+    /// * [`SimulationError::InvalidStatus`] if a status is given for plankton or the status of a
+    ///   fish is used for a shark or the other way round
+    pub fn place(
+        &mut self,
+        x: u32,
+        y: u32,
+        r#type: FieldType,
+        status: Option<AnimalStatus>,
+    ) -> Result<Field> {
+        self.check_bounds(x, y)?;
+        let field = match status {
+            None => self.spawn(r#type, x, y),
+            Some(mut status) => {
+                let matches = match r#type {
+                    FieldType::Fish => !status.is_shark(),
+                    FieldType::Shark => status.is_shark(),
+                    FieldType::Plankton => false,
+                };
+                if !matches {
+                    return Err(SimulationError::InvalidStatus { x, y, r#type });
+                }
+                self.register(&r#type, &mut status, None);
+                Field::new(r#type, x, y, Some(status))
+            }
+        };
+
+        let replaced = std::mem::replace(&mut self.fields[y as usize][x as usize], field);
+        if let (Some(lineage), Some(status)) = (self.lineage.as_mut(), &replaced.status) {
+            lineage.record_death(status.id(), self.current_step);
+        }
+        if self.current_step == 0 {
+            self.record_initial_state();
+        }
+        Ok(replaced)
+    }
+
+    /// Removes the animal from a field
+    ///
+    /// The animal is logged as dead like an animal that is replaced with [`Board::place`].
+    ///
+    /// # Arguments
+    /// * `x` - The column of the field
+    /// * `y` - The row of the field
+    ///
+    /// # Returns
+    /// The field that was cleared
+    ///
+    /// # Errors
+    /// * [`SimulationError::OutOfBounds`] if the position is outside of the board
+    pub fn clear(&mut self, x: u32, y: u32) -> Result<Field> {
+        self.place(x, y, FieldType::Plankton, None)
+    }
+
+    /// Creates a field with a freshly born animal that gets a new id
+    fn spawn(&mut self, r#type: FieldType, x: u32, y: u32) -> Field {
+        let mut field = Field::spawn(r#type, x, y, &self.config, &mut self.rng);
+        if let Some(status) = field.status.as_mut() {
+            self.register(&field.r#type, status, None);
+        }
+        field
+    }
+
+    /// Creates a field with an animal that is born during the current step
+    fn give_birth(&mut self, parent: &Field, x: u32, y: u32) -> Field {
+        let mut field = Field::spawn(parent.r#type.clone(), x, y, &self.config, &mut self.rng);
+        let parent_id = parent.status.as_ref().map(|status| status.id());
+        if let Some(status) = field.status.as_mut() {
+            self.register(&field.r#type, status, parent_id);
+        }
+        field
+    }
+
+    /// Gives an animal a new id and logs its birth
+    ///
+    /// Animals with a parent are born during a step and are logged with the number of that step.
+    fn register(&mut self, r#type: &FieldType, status: &mut AnimalStatus, parent_id: Option<u64>) {
+        let id = self.next_id;
+        self.next_id += 1;
+        status.assign_id(id, parent_id);
+
+        if let Some(lineage) = self.lineage.as_mut() {
+            let step = self.current_step + u32::from(parent_id.is_some());
+            lineage.record_birth(id, parent_id, r#type.clone(), step);
+        }
+    }
+
+    /// Logs that an animal was eaten or starved during the current step
+    fn record_death(&mut self, animal: &Field) {
+        self.statistics.record_death(animal);
+        if let (Some(lineage), Some(status)) = (self.lineage.as_mut(), &animal.status) {
+            lineage.record_death(status.id(), self.current_step + 1);
+        }
+    }
+
+    fn check_bounds(&self, x: u32, y: u32) -> Result {
+        if x >= self.columns || y >= self.rows {
+            return Err(SimulationError::OutOfBounds {
+                x,
+                y,
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the fields of the board match its dimensions and that every animal has a
+    /// status
+    ///
+    /// # Errors
+    /// * [`SimulationError::InvalidDimensions`] if the board has no rows or columns
+    /// * [`SimulationError::InvalidSnapshot`] if there are more animals than fields, the fields
+    ///   are inconsistent, e.g. a status does not match the type of its field, a shark has no
+    ///   life left or an id is used twice or not below the next id, or an animal in the lineage
+    ///   log is its own ancestor
+    /// * [`SimulationError::InvalidConfig`] if a parameter of the config is invalid
+    #[cfg(feature = "serde")]
+    pub(crate) fn validate(&self) -> Result {
+        if self.rows == 0 || self.columns == 0 || self.rows.checked_mul(self.columns).is_none() {
+            return Err(SimulationError::InvalidDimensions {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let invalid = |message: String| Err(SimulationError::InvalidSnapshot { message });
+        let animals = u64::from(self.amount_fishes) + u64::from(self.amount_sharks);
+        if animals > u64::from(self.rows) * u64::from(self.columns) {
+            return invalid(format!(
+                "{animals} animals do not fit on a board with {}x{} fields",
+                self.rows, self.columns
+            ));
+        }
+        if self.fields.len() != self.rows as usize {
+            return invalid(format!(
+                "{} rows of fields for a board with {} rows",
+                self.fields.len(),
+                self.rows
+            ));
+        }
+        let mut ids = BTreeSet::new();
+        for (y, row) in self.fields.iter().enumerate() {
+            if row.len() != self.columns as usize {
+                return invalid(format!(
+                    "row {y} has {} fields instead of {}",
+                    row.len(),
+                    self.columns
+                ));
+            }
+            for (x, field) in row.iter().enumerate() {
+                if (field.x as usize, field.y as usize) != (x, y) {
+                    return invalid(format!(
+                        "field ({}, {}) is stored at ({x}, {y})",
+                        field.x, field.y
+                    ));
+                }
+                let Some(status) = &field.status else {
+                    if field.r#type != FieldType::Plankton {
+                        return invalid(format!("the animal on field ({x}, {y}) has no status"));
+                    }
+                    continue;
+                };
+                if field.r#type == FieldType::Plankton {
+                    return invalid(format!("the plankton on field ({x}, {y}) has a status"));
+                }
+                if status.is_shark() != (field.r#type == FieldType::Shark) {
+                    return invalid(format!(
+                        "field ({x}, {y}) has a status that does not match its type"
+                    ));
+                }
+                if status.remaining_life() == Some(0) {
+                    return invalid(format!("the shark on field ({x}, {y}) has no life left"));
+                }
+                if status.id() >= self.next_id {
+                    return invalid(format!(
+                        "the animal on field ({x}, {y}) has the id {} but the next id is {}",
+                        status.id(),
+                        self.next_id
+                    ));
+                }
+                if !ids.insert(status.id()) {
+                    return invalid(format!(
+                        "the animal on field ({x}, {y}) has the id {} of another animal",
+                        status.id()
+                    ));
+                }
+            }
+        }
+        if let Some(id) = self.lineage.as_ref().and_then(Lineage::find_cycle) {
+            return invalid(format!("the animal with the id {id} is its own ancestor"));
+        }
+
+        self.config.validate()
+    }
+
+    /// Returns the statistics of all steps that were simulated so far
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Returns the amount of fields, [`Board::new`] and [`Board::validate`] ensure that it fits
+    /// into a `u32`
+    fn amount_fields(&self) -> u32 {
+        self.rows * self.columns
+    }
+
+    /// Restarts the statistics with the current state of the board
+    fn record_initial_state(&mut self) {
+        let (fishes, sharks) = self.count_animals();
+        self.statistics = Statistics::default();
+        self.statistics.record(&StepReport {
+            step: self.current_step,
+            fishes,
+            sharks,
+            plankton: self.amount_fields() - fishes - sharks,
+            ..StepReport::default()
+        });
+        self.statistics.record_ages(self.fields.iter().flatten());
+    }
+
     /// Simulates one step of the simulation
     ///
+    /// If a species dies out during the step a [`SimulationEvent`] is added to the report. The
+    /// surviving population keeps being simulated unless the [`ExtinctionPolicy`] says otherwise.
+    ///
     /// # Errors
-    /// If there are no sharks or fishes on the board
-    pub fn step(&mut self) -> Result {
+    /// * [`SimulationError::FishExtinct`] if there are no fishes on the board and the policy
+    ///   stops on extinct fishes
+    /// * [`SimulationError::SharksExtinct`] if there are no sharks on the board and the policy
+    ///   stops on extinct sharks
+    pub fn step(&mut self) -> Result<StepReport> {
         let cloned_fields = self.fields.clone();
         let fishes = Self::get_fishes(&cloned_fields);
         let sharks = Self::get_sharks(&cloned_fields);
+        let (fishes_before, sharks_before) = (fishes.len() as u32, sharks.len() as u32);
 
-        if fishes.is_empty() || sharks.is_empty() {
-            return Err(SimulationError(
-                "No fishes or sharks left on the board".into(),
-            ));
+        if fishes.is_empty() && self.extinction_policy.stops_on_fish() {
+            return Err(SimulationError::FishExtinct {
+                step: self.current_step,
+                sharks: sharks_before,
+            });
+        }
+        if sharks.is_empty() && self.extinction_policy.stops_on_sharks() {
+            return Err(SimulationError::SharksExtinct {
+                step: self.current_step,
+                fishes: fishes_before,
+            });
+        }
+
+        let mut report = StepReport::default();
+        let fishes: Vec<Position> = fishes.iter().map(|fish| (fish.x, fish.y)).collect();
+        let sharks: Vec<Position> = sharks.iter().map(|shark| (shark.x, shark.y)).collect();
+        match self.update_order {
+            UpdateOrder::Sequential => self.step_sequential(fishes, sharks, &mut report),
+            UpdateOrder::Shuffled => {
+                let (mut fishes, mut sharks) = (fishes, sharks);
+                fishes.shuffle(&mut self.rng);
+                sharks.shuffle(&mut self.rng);
+                self.step_sequential(fishes, sharks, &mut report);
+            }
+            UpdateOrder::Synchronous => self.step_synchronous(&mut report),
+        }
+
+        self.current_step += 1;
+
+        let (fishes, sharks) = self.count_animals();
+        debug_assert_eq!(
+            fishes + report.fishes_eaten,
+            fishes_before + report.fish_births
+        );
+        debug_assert_eq!(
+            sharks + report.sharks_starved,
+            sharks_before + report.shark_births
+        );
+        if fishes_before > 0 && fishes == 0 {
+            report.events.push(SimulationEvent::FishExtinct);
+        }
+        if sharks_before > 0 && sharks == 0 {
+            report.events.push(SimulationEvent::SharksExtinct);
+        }
+
+        report.step = self.current_step;
+        report.fishes = fishes;
+        report.sharks = sharks;
+        report.plankton = self.amount_fields() - fishes - sharks;
+        self.statistics.record(&report);
+        self.statistics.record_ages(self.fields.iter().flatten());
+        Ok(report)
+    }
+
+    /// Lets the animals act one after another on the current state of the board
+    ///
+    /// Every animal acts exactly once: the fields an animal moved to and the fields of newborns
+    /// are marked, so they are skipped if they show up later in the order.
+    ///
+    /// # Arguments
+    /// * `fishes` - The positions of the fishes in the order they act
+    /// * `sharks` - The positions of the sharks in the order they act
+    /// * `report` - The report the births and deaths are counted in
+    fn step_sequential(
+        &mut self,
+        fishes: Vec<Position>,
+        sharks: Vec<Position>,
+        report: &mut StepReport,
+    ) {
+        let mut acted = vec![vec![false; self.columns as usize]; self.rows as usize];
+
+        for (r#type, positions) in [(FieldType::Fish, fishes), (FieldType::Shark, sharks)] {
+            for (x, y) in positions {
+                let animal = self.fields[y as usize][x as usize].clone();
+                if animal.r#type != r#type || acted[y as usize][x as usize] {
+                    continue;
+                }
+
+                let decision = animal.step(
+                    &self.fields,
+                    &self.config,
+                    self.topology,
+                    self.neighbourhood,
+                    &mut self.rng,
+                );
+                for (x, y) in self.move_animal(&animal, decision, &acted, report) {
+                    acted[y as usize][x as usize] = true;
+                }
+            }
+
+            if r#type == FieldType::Fish {
+                debug!("After fish moves:\n{}", self);
+            }
         }
+    }
 
-        for fish in fishes {
-            let (old_x, old_y) = (fish.x, fish.y);
-            let ((new_x, new_y), status) = fish.step(&self.fields).unwrap();
+    /// Moves an animal to its new position and leaves a newborn behind if it has to breed
+    ///
+    /// A newborn is only placed if the animal actually left its field.
+    ///
+    /// # Arguments
+    /// * `animal` - The animal before the move
+    /// * `decision` - The new position and status of the animal or `None` if it died
+    /// * `acted` - The fields of the animals that already acted during the step
+    /// * `report` - The report the births and deaths are counted in
+    ///
+    /// # Returns
+    /// The fields that are occupied by the animal and its newborn after the move
+    fn move_animal(
+        &mut self,
+        animal: &Field,
+        decision: Option<(Position, Option<AnimalStatus>)>,
+        acted: &[Vec<bool>],
+        report: &mut StepReport,
+    ) -> Vec<Position> {
+        let (old_x, old_y) = (animal.x, animal.y);
+        let Some(((new_x, new_y), status)) = decision else {
+            // Set old field to plankton
+            self.fields[old_y as usize][old_x as usize] =
+                Field::new(FieldType::Plankton, old_x, old_y, None);
+            report.sharks_starved += 1;
+            self.record_death(animal);
+            return vec![];
+        };
 
+        let mut occupied = vec![(new_x, new_y)];
+        if (new_x, new_y) != (old_x, old_y) {
+            report.count_move(&animal.r#type);
             if status.as_ref().unwrap().has_to_breed() {
-                self.fields[old_y as usize][old_x as usize] =
-                    Field::new(FieldType::Fish, old_x, old_y, None);
+                self.fields[old_y as usize][old_x as usize] = self.give_birth(animal, old_x, old_y);
+                report.count_birth(&animal.r#type);
+                occupied.push((old_x, old_y));
             } else {
                 // Set old field to plankton
                 self.fields[old_y as usize][old_x as usize] =
                     Field::new(FieldType::Plankton, old_x, old_y, None);
             }
-            // Set new field to fish
-            self.fields[new_y as usize][new_x as usize] =
-                Field::new(FieldType::Fish, new_x, new_y, status);
         }
 
-        debug!("After fish moves:\n{}", self);
+        let target = &self.fields[new_y as usize][new_x as usize];
+        if target.r#type == FieldType::Fish && (new_x, new_y) != (old_x, old_y) {
+            report.fishes_eaten += 1;
+            let mut prey = target.clone();
+            if acted[new_y as usize][new_x as usize] {
+                // The fish already grew older during this step. As in the synchronous update its
+                // lifespan is its age before the step.
+                prey.status = prey.status.map(|status| {
+                    let age = status.age().saturating_sub(1);
+                    status.with_age(age)
+                });
+            }
+            self.record_death(&prey);
+        }
 
-        for shark in sharks {
-            let (old_x, old_y) = (shark.x, shark.y);
-            if let Some(((new_x, new_y), status)) = shark.step(&self.fields) {
-                if status.as_ref().unwrap().has_to_breed() {
-                    self.fields[old_y as usize][old_x as usize] =
-                        Field::new(FieldType::Shark, old_x, old_y, None);
-                } else {
-                    // Set old field to plankton
-                    self.fields[old_y as usize][old_x as usize] =
-                        Field::new(FieldType::Plankton, old_x, old_y, None);
+        // Set new field to the animal
+        self.fields[new_y as usize][new_x as usize] =
+            Field::new(animal.r#type.clone(), new_x, new_y, status);
+        occupied
+    }
+
+    /// Lets all animals decide based on the state before the step and resolves conflicts
+    /// randomly
+    ///
+    /// # Arguments
+    /// * `report` - The report the births and deaths are counted in
+    fn step_synchronous(&mut self, report: &mut StepReport) {
+        let previous = self.fields.clone();
+        let animals: Vec<&Field> = previous
+            .iter()
+            .flatten()
+            .filter(|field| field.r#type != FieldType::Plankton)
+            .collect();
+        let mut decisions: Vec<Option<(Position, Option<AnimalStatus>)>> = animals
+            .iter()
+            .map(|animal| {
+                animal.step(
+                    &previous,
+                    &self.config,
+                    self.topology,
+                    self.neighbourhood,
+                    &mut self.rng,
+                )
+            })
+            .collect();
+
+        // Sharks that want to eat the same fish
+        let mut prey_claims: BTreeMap<Position, Vec<usize>> = BTreeMap::new();
+        for (index, decision) in decisions.iter().enumerate() {
+            if let Some((target, _)) = decision {
+                if previous[target.1 as usize][target.0 as usize].r#type == FieldType::Fish {
+                    prey_claims.entry(*target).or_default().push(index);
                 }
+            }
+        }
 
-                // Set new field to shark
-                self.fields[new_y as usize][new_x as usize] =
-                    Field::new(FieldType::Shark, new_x, new_y, status);
-            } else {
-                // Set old field to plankton
-                self.fields[old_y as usize][old_x as usize] =
-                    Field::new(FieldType::Plankton, old_x, old_y, None);
+        let mut eaten = BTreeSet::new();
+        for (prey, claimants) in prey_claims {
+            let winner = claimants[self.rng.gen_range(0..claimants.len())];
+            eaten.insert(prey);
+            for loser in claimants.into_iter().filter(|index| *index != winner) {
+                let shark = animals[loser];
+                decisions[loser] = shark
+                    .stay(&self.config)
+                    .map(|status| ((shark.x, shark.y), Some(status)));
+            }
+        }
+        for (animal, decision) in animals.iter().zip(decisions.iter_mut()) {
+            if animal.r#type == FieldType::Fish && eaten.contains(&(animal.x, animal.y)) {
+                *decision = None;
+                report.fishes_eaten += 1;
+                self.record_death(animal);
+            } else if animal.r#type == FieldType::Shark && decision.is_none() {
+                report.sharks_starved += 1;
+                self.record_death(animal);
             }
         }
 
-        Ok(())
+        // Animals that want to move to the same free field
+        let mut move_claims: BTreeMap<Position, Vec<usize>> = BTreeMap::new();
+        for (index, (animal, decision)) in animals.iter().zip(decisions.iter()).enumerate() {
+            if let Some((target, _)) = decision {
+                if *target != (animal.x, animal.y) && !eaten.contains(target) {
+                    move_claims.entry(*target).or_default().push(index);
+                }
+            }
+        }
+
+        for (_, claimants) in move_claims {
+            let winner = claimants[self.rng.gen_range(0..claimants.len())];
+            for loser in claimants.into_iter().filter(|index| *index != winner) {
+                let animal = animals[loser];
+                if let Some((target, _)) = decisions[loser].as_mut() {
+                    *target = (animal.x, animal.y);
+                }
+            }
+        }
+
+        let mut fields = Self::empty_fields(self.rows, self.columns);
+        for (animal, decision) in animals.iter().zip(decisions) {
+            if let Some(((new_x, new_y), status)) = decision {
+                let moved = (new_x, new_y) != (animal.x, animal.y);
+                if moved {
+                    report.count_move(&animal.r#type);
+                }
+                if moved && status.as_ref().unwrap().has_to_breed() {
+                    fields[animal.y as usize][animal.x as usize] =
+                        self.give_birth(animal, animal.x, animal.y);
+                    report.count_birth(&animal.r#type);
+                }
+                fields[new_y as usize][new_x as usize] =
+                    Field::new(animal.r#type.clone(), new_x, new_y, status);
+            }
+        }
+        self.fields = fields;
+    }
+
+    /// Creates a board that only contains plankton
+    fn empty_fields(rows: u32, columns: u32) -> Vec<Vec<Field>> {
+        let mut fields: Vec<Vec<Field>> = Vec::with_capacity(rows as usize);
+        for y in 0..rows {
+            let mut row = Vec::with_capacity(columns as usize);
+            for x in 0..columns {
+                row.push(Field::new(FieldType::Plankton, x, y, None));
+            }
+            fields.push(row)
+        }
+        fields
     }
 
     fn get_fishes(animals: &[Vec<Field>]) -> Vec<&Field> {
@@ -201,3 +882,580 @@ impl Display for Board {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn create_board(layout: &[&str], config: SimulationConfig) -> Board {
+        let rows = layout.len() as u32;
+        let columns = layout[0].len() as u32;
+        let mut board = Board::new(0, 0, rows, columns)
+            .with_seed(1)
+            .with_config(config)
+            .unwrap();
+        board.fields = Board::empty_fields(rows, columns);
+        for (y, row) in layout.iter().enumerate() {
+            for (x, glyph) in row.chars().enumerate() {
+                let r#type = match glyph {
+                    'F' => FieldType::Fish,
+                    'S' => FieldType::Shark,
+                    _ => continue,
+                };
+                board.fields[y][x] = board.spawn(r#type, x as u32, y as u32);
+            }
+        }
+        board
+    }
+
+    fn hungry_config() -> SimulationConfig {
+        SimulationConfig {
+            initial_shark_life: 5..6,
+            ..SimulationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_accessors_and_iterators() {
+        let mut board = create_board(&["F_S_", "__F_", "S___"], SimulationConfig::default());
+        assert_eq!((board.rows(), board.columns()), (3, 4));
+        assert_eq!(board.current_step(), 0);
+
+        assert_eq!(board.cells().count(), 12);
+        assert!(board
+            .cells()
+            .enumerate()
+            .all(|(index, field)| (field.x + field.y * 4) as usize == index));
+        let fishes: Vec<Position> = board.fishes().map(|(position, _)| position).collect();
+        assert_eq!(fishes, vec![(0, 0), (2, 1)]);
+        let sharks: Vec<Position> = board.sharks().map(|(position, _)| position).collect();
+        assert_eq!(sharks, vec![(2, 0), (0, 2)]);
+
+        board.step().unwrap();
+        board.step().unwrap();
+        assert_eq!(board.current_step(), 2);
+        let (fishes, sharks) = board.count_animals();
+        assert_eq!(board.fishes().count() as u32, fishes);
+        assert_eq!(board.sharks().count() as u32, sharks);
+    }
+
+    #[test]
+    fn test_place_and_clear() {
+        let mut board = Board::new(0, 0, 3, 4).with_seed(1);
+        assert_eq!(board.count_animals(), (0, 0));
+
+        board.place(3, 2, FieldType::Shark, None).unwrap();
+        board.place(0, 0, FieldType::Fish, None).unwrap();
+        assert_eq!(board.get(3, 2).unwrap().r#type, FieldType::Shark);
+        assert_eq!(board.count_animals(), (1, 1));
+        assert_eq!(board.statistics().records()[0].plankton, 10);
+
+        let shark = board.clear(3, 2).unwrap();
+        assert_eq!(shark.r#type, FieldType::Shark);
+        assert_eq!(board.get(3, 2).unwrap().r#type, FieldType::Plankton);
+        assert_eq!(board.get(3, 2).unwrap().status, None);
+        assert_eq!(board.count_animals(), (1, 0));
+
+        // An animal keeps its status apart from its id when it is moved by hand
+        let status = shark.status.unwrap();
+        board
+            .place(2, 1, FieldType::Shark, Some(status.clone()))
+            .unwrap();
+        let moved = board.get(2, 1).unwrap().status.clone().unwrap();
+        assert_eq!(moved.remaining_life(), status.remaining_life());
+        assert_eq!(moved.turns_until_breeding(), status.turns_until_breeding());
+        assert_eq!(moved.age(), status.age());
+        assert_ne!(moved.id(), status.id());
+        assert_eq!(
+            (board.get(2, 1).unwrap().x, board.get(2, 1).unwrap().y),
+            (2, 1)
+        );
+    }
+
+    #[test]
+    fn test_place_errors() {
+        let mut board = Board::new(0, 0, 3, 4);
+        assert_eq!(board.get(4, 0), None);
+        assert_eq!(board.get(0, 3), None);
+        assert_eq!(
+            board.place(4, 0, FieldType::Fish, None),
+            Err(SimulationError::OutOfBounds {
+                x: 4,
+                y: 0,
+                rows: 3,
+                columns: 4
+            })
+        );
+        assert!(matches!(
+            board.clear(0, 3),
+            Err(SimulationError::OutOfBounds { .. })
+        ));
+
+        board.place(0, 0, FieldType::Fish, None).unwrap();
+        let fish = board.get(0, 0).unwrap().status.clone();
+        assert_eq!(
+            board.place(1, 1, FieldType::Shark, fish.clone()),
+            Err(SimulationError::InvalidStatus {
+                x: 1,
+                y: 1,
+                r#type: FieldType::Shark
+            })
+        );
+        assert!(board.place(1, 1, FieldType::Plankton, fish).is_err());
+        assert_eq!(board.count_animals(), (1, 0));
+    }
+
+    #[test]
+    fn test_place_ids_and_deaths() {
+        let mut board = Board::new(0, 0, 3, 4).with_seed(1).with_lineage_log();
+        board.place(0, 0, FieldType::Shark, None).unwrap();
+        board.place(1, 0, FieldType::Fish, None).unwrap();
+        let shark = board.get(0, 0).unwrap().status.clone().unwrap();
+        let fish = board.get(1, 0).unwrap().status.clone().unwrap();
+
+        // Moving an animal by hand logs it as a new animal
+        board.clear(0, 0).unwrap();
+        board
+            .place(2, 2, FieldType::Shark, Some(shark.clone()))
+            .unwrap();
+        let moved = board.get(2, 2).unwrap().status.clone().unwrap();
+        let lineage = board.lineage().unwrap();
+        assert_eq!(lineage.get(shark.id()).unwrap().died, Some(0));
+        assert_eq!(lineage.get(moved.id()).unwrap().died, None);
+        assert_eq!(board.statistics().shark_lifespans().total(), 0);
+
+        // A copy of an animal on the board or of a dead animal gets an unused id
+        board
+            .place(3, 2, FieldType::Fish, Some(fish.clone()))
+            .unwrap();
+        board
+            .place(3, 1, FieldType::Shark, Some(shark.clone()))
+            .unwrap();
+        let mut ids: Vec<u64> = board
+            .fields
+            .iter()
+            .flatten()
+            .filter_map(|field| field.status.as_ref().map(AnimalStatus::id))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+        assert!(!ids.contains(&shark.id()));
+
+        // Ids of other boards are not kept, so they cannot run out
+        let mut foreign = AnimalStatus::fish(3);
+        foreign.assign_id(u64::MAX, None);
+        let replaced = board.place(1, 0, FieldType::Fish, Some(foreign)).unwrap();
+        assert_eq!(replaced.status, Some(fish.clone()));
+        assert_ne!(
+            board.get(1, 0).unwrap().status.as_ref().unwrap().id(),
+            u64::MAX
+        );
+        assert_eq!(
+            board.lineage().unwrap().get(fish.id()).unwrap().died,
+            Some(0)
+        );
+        assert_eq!(board.statistics().fish_lifespans().total(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bigger than the amount of fields")]
+    fn test_new_population_does_not_overflow() {
+        Board::new(u32::MAX, 1, 2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into a u32")]
+    fn test_new_too_many_fields() {
+        Board::new(0, 0, 1 << 16, 1 << 16);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = SimulationConfig {
+            initial_shark_life: 4..4,
+            ..SimulationConfig::default()
+        };
+        let result = Board::new(0, 1, 2, 2).with_config(config);
+        assert!(matches!(
+            result,
+            Err(SimulationError::InvalidConfig { key, .. }) if key == "initial_shark_life"
+        ));
+    }
+
+    #[test]
+    fn test_same_seed_same_history() {
+        let mut first = Board::new(30, 10, 12, 12).with_seed(7);
+        let mut second = Board::new(30, 10, 12, 12).with_seed(7);
+        first.generate_random_animals();
+        second.generate_random_animals();
+        assert_eq!(first, second);
+
+        for _ in 0..25 {
+            assert_eq!(first.step(), second.step());
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_different_seed_different_placement() {
+        let mut first = Board::new(30, 10, 12, 12).with_seed(1);
+        let mut second = Board::new(30, 10, 12, 12).with_seed(2);
+        first.generate_random_animals();
+        second.generate_random_animals();
+        assert_ne!(first.fields, second.fields);
+    }
+
+    #[test]
+    fn test_non_square_boards() {
+        for (rows, columns) in [(3, 17), (17, 3), (1, 9), (9, 1)] {
+            let mut board = Board::new(4, 2, rows, columns).with_seed(5);
+            board.generate_random_animals();
+            for _ in 0..30 {
+                board.step().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_topologies() {
+        for topology in [
+            Topology::Torus,
+            Topology::Box,
+            Topology::HorizontalCylinder,
+            Topology::VerticalCylinder,
+        ] {
+            let mut board = Board::new(20, 6, 7, 11)
+                .with_seed(9)
+                .with_topology(topology);
+            board.generate_random_animals();
+            for _ in 0..30 {
+                board.step().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_neighbourhoods() {
+        for neighbourhood in [
+            Neighbourhood::VonNeumann,
+            Neighbourhood::Moore,
+            Neighbourhood::Hexagonal,
+        ] {
+            for topology in [Topology::Torus, Topology::Box] {
+                let mut board = Board::new(20, 6, 8, 11)
+                    .with_seed(9)
+                    .with_topology(topology)
+                    .with_neighbourhood(neighbourhood);
+                board.generate_random_animals();
+                for _ in 0..30 {
+                    board.step().unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_update_orders_are_deterministic() {
+        for update_order in [
+            UpdateOrder::Sequential,
+            UpdateOrder::Shuffled,
+            UpdateOrder::Synchronous,
+        ] {
+            let mut first = Board::new(40, 10, 10, 12)
+                .with_seed(4)
+                .with_update_order(update_order);
+            let mut second = Board::new(40, 10, 10, 12)
+                .with_seed(4)
+                .with_update_order(update_order);
+            first.generate_random_animals();
+            second.generate_random_animals();
+            for _ in 0..30 {
+                assert_eq!(first.step(), second.step());
+            }
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_synchronous_sharks_see_fish_before_it_moves() {
+        let layout = ["_____", "_SFS_", "_____"];
+
+        let mut board =
+            create_board(&layout, hungry_config()).with_update_order(UpdateOrder::Synchronous);
+        board.step().unwrap();
+        assert_eq!(board.count_animals(), (0, 2));
+
+        let mut board =
+            create_board(&layout, hungry_config()).with_update_order(UpdateOrder::Sequential);
+        board.step().unwrap();
+        assert_eq!(board.count_animals(), (1, 2));
+    }
+
+    #[test]
+    fn test_synchronous_conflicting_moves() {
+        let mut board = create_board(&["F_F"], SimulationConfig::default())
+            .with_topology(Topology::Box)
+            .with_update_order(UpdateOrder::Synchronous);
+        board.step().unwrap();
+        assert_eq!(board.count_animals(), (2, 0));
+        assert_eq!(board.fields[0][1].r#type, FieldType::Fish);
+    }
+
+    #[test]
+    fn test_animal_moves_only_once() {
+        for update_order in [
+            UpdateOrder::Sequential,
+            UpdateOrder::Shuffled,
+            UpdateOrder::Synchronous,
+        ] {
+            let mut board = create_board(&["F_____"], SimulationConfig::default())
+                .with_topology(Topology::Box)
+                .with_update_order(update_order);
+            board.step().unwrap();
+            assert_eq!(board.fields[0][1].r#type, FieldType::Fish);
+            assert_eq!(board.count_animals(), (1, 0));
+        }
+    }
+
+    #[test]
+    fn test_no_newborn_without_move() {
+        let config = SimulationConfig {
+            fish_breed_time: 1,
+            ..SimulationConfig::default()
+        };
+        let mut board = create_board(&["FF", "FF"], config).with_topology(Topology::Box);
+        let report = board.step().unwrap();
+        assert_eq!(report.fish_births, 0);
+        assert_eq!(board.count_animals(), (4, 0));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(48))]
+
+        #[test]
+        fn prop_animals_are_conserved(
+            seed in any::<u64>(),
+            rows in 1u32..12,
+            columns in 1u32..12,
+            density in 0.0f64..1.0,
+            update_order in prop_oneof![
+                Just(UpdateOrder::Sequential),
+                Just(UpdateOrder::Shuffled),
+                Just(UpdateOrder::Synchronous),
+            ],
+            neighbourhood in prop_oneof![
+                Just(Neighbourhood::VonNeumann),
+                Just(Neighbourhood::Moore),
+                Just(Neighbourhood::Hexagonal),
+            ],
+        ) {
+            let fields = rows * columns;
+            let animals = (f64::from(fields) * density) as u32;
+            let mut board = Board::new(animals - animals / 4, animals / 4, rows, columns)
+                .with_seed(seed)
+                .with_update_order(update_order)
+                .with_neighbourhood(neighbourhood);
+            board.generate_random_animals();
+
+            for _ in 0..20 {
+                let (fishes, sharks) = board.count_animals();
+                let report = board.step().unwrap();
+                prop_assert_eq!(
+                    report.fishes,
+                    fishes + report.fish_births - report.fishes_eaten
+                );
+                prop_assert_eq!(
+                    report.sharks,
+                    sharks + report.shark_births - report.sharks_starved
+                );
+                prop_assert_eq!((report.fishes, report.sharks), board.count_animals());
+                prop_assert!(board.fields.iter().enumerate().all(|(y, row)| row
+                    .iter()
+                    .enumerate()
+                    .all(|(x, field)| (field.x, field.y) == (x as u32, y as u32))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_statistics_history() {
+        let mut board = Board::new(30, 10, 10, 10).with_seed(8);
+        board.generate_random_animals();
+        assert_eq!(board.statistics().len(), 1);
+        assert_eq!(board.statistics().records()[0].fishes, 30);
+        assert_eq!(board.statistics().records()[0].sharks, 10);
+
+        let mut reports = vec![];
+        for _ in 0..15 {
+            reports.push(board.step().unwrap());
+        }
+
+        let statistics = board.statistics();
+        assert_eq!(statistics.len(), 16);
+        assert_eq!(&statistics.records()[1..], &reports[..]);
+        assert_eq!(statistics.last(), reports.last());
+        assert!(statistics.iter().map(|record| record.step).eq(0..16));
+        assert!(statistics
+            .fishes()
+            .zip(statistics.sharks())
+            .skip(1)
+            .eq(reports.iter().map(|report| (report.fishes, report.sharks))));
+        assert!(statistics
+            .iter()
+            .skip(1)
+            .any(|record| record.fish_moves > 0));
+    }
+
+    #[test]
+    fn test_animal_ids_are_unique() {
+        let mut board = Board::new(40, 10, 10, 10).with_seed(3);
+        board.generate_random_animals();
+        for _ in 0..20 {
+            board.step().unwrap();
+            let mut ids: Vec<u64> = board
+                .fishes()
+                .chain(board.sharks())
+                .map(|(_, status)| status.id())
+                .collect();
+            let amount = ids.len();
+            ids.sort_unstable();
+            ids.dedup();
+            assert_eq!(ids.len(), amount);
+            assert!(!ids.contains(&0));
+        }
+
+        board
+            .place(0, 0, FieldType::Fish, Some(AnimalStatus::fish(1)))
+            .unwrap();
+        assert_ne!(board.get(0, 0).unwrap().status.as_ref().unwrap().id(), 0);
+    }
+
+    #[test]
+    fn test_lineage_log() {
+        for update_order in [UpdateOrder::Sequential, UpdateOrder::Synchronous] {
+            let mut board = Board::new(30, 8, 10, 10)
+                .with_seed(5)
+                .with_update_order(update_order)
+                .with_lineage_log();
+            board.generate_random_animals();
+            let (mut births, mut deaths) = (0, 0);
+            for _ in 0..30 {
+                let report = board.step().unwrap();
+                births += report.fish_births + report.shark_births;
+                deaths += report.fishes_eaten + report.sharks_starved;
+            }
+
+            let lineage = board.lineage().unwrap();
+            assert_eq!(lineage.len() as u32, 38 + births);
+            assert_eq!(
+                lineage
+                    .iter()
+                    .filter(|record| record.died.is_some())
+                    .count() as u32,
+                deaths
+            );
+            for record in lineage.iter() {
+                if let Some(parent_id) = record.parent_id {
+                    let parent = lineage.get(parent_id).unwrap();
+                    assert_eq!(parent.r#type, record.r#type);
+                    assert!(parent.born < record.born);
+                }
+            }
+
+            // Every animal on the board is alive in the log
+            for (_, status) in board.fishes().chain(board.sharks()) {
+                let record = lineage.get(status.id()).unwrap();
+                assert_eq!(record.parent_id, status.parent_id());
+                assert_eq!(record.died, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_age_statistics() {
+        let config = SimulationConfig {
+            shark_starvation_time: 2,
+            initial_shark_life: 2..3,
+            ..SimulationConfig::default()
+        };
+        for update_order in [UpdateOrder::Sequential, UpdateOrder::Synchronous] {
+            // The shark eats the fish and starves two steps later
+            let mut board = create_board(&["FS"], config.clone())
+                .with_topology(Topology::Box)
+                .with_update_order(update_order);
+            board.record_initial_state();
+            assert_eq!(board.statistics().fish_ages().counts(), &[1]);
+            assert_eq!(board.statistics().shark_ages().counts(), &[1]);
+
+            board.step().unwrap();
+            let statistics = board.statistics();
+            assert_eq!(statistics.fish_ages().total(), 0);
+            assert_eq!(statistics.shark_ages().counts(), &[0, 1]);
+            assert_eq!(statistics.fish_lifespans().counts(), &[1]);
+
+            board.step().unwrap();
+            board.step().unwrap();
+            let statistics = board.statistics();
+            assert_eq!(statistics.shark_ages().total(), 0);
+            assert_eq!(statistics.shark_lifespans().counts(), &[0, 0, 1]);
+            assert_eq!(statistics.shark_lifespans().mean(), Some(2.0));
+        }
+    }
+
+    #[test]
+    fn test_fishes_breed_without_sharks() {
+        let mut board = Board::new(3, 0, 6, 6).with_seed(1);
+        board.generate_random_animals();
+
+        let report = board.step().unwrap();
+        assert_eq!(report.step, 1);
+        assert_eq!((report.fishes, report.sharks), (3, 0));
+        assert!(report.events.is_empty());
+
+        for _ in 0..5 {
+            board.step().unwrap();
+        }
+        assert!(board.count_animals().0 > 3);
+    }
+
+    #[test]
+    fn test_sharks_extinct_event() {
+        let mut board = Board::new(0, 4, 6, 6).with_seed(1);
+        board.generate_random_animals();
+
+        let mut extinction_events = 0;
+        for _ in 0..20 {
+            let report = board.step().unwrap();
+            extinction_events += report
+                .events
+                .iter()
+                .filter(|event| **event == SimulationEvent::SharksExtinct)
+                .count();
+        }
+        assert_eq!(board.count_animals(), (0, 0));
+        assert_eq!(extinction_events, 1);
+    }
+
+    #[test]
+    fn test_extinction_errors() {
+        let mut board = Board::new(3, 0, 4, 4)
+            .with_seed(1)
+            .with_extinction_policy(ExtinctionPolicy::StopOnSharksExtinct);
+        board.generate_random_animals();
+        assert_eq!(
+            board.step(),
+            Err(SimulationError::SharksExtinct { step: 0, fishes: 3 })
+        );
+
+        let mut board = Board::new(0, 2, 4, 4)
+            .with_seed(1)
+            .with_extinction_policy(ExtinctionPolicy::StopOnFishExtinct);
+        board.generate_random_animals();
+        assert_eq!(
+            board.step(),
+            Err(SimulationError::FishExtinct { step: 0, sharks: 2 })
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Contains the builder for a board. The builder validates all parameters before a board is created.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Board;
+//!
+//! // Board with 10 fishes and 5 sharks with 25x25 fields
+//! let board = Board::builder()
+//!     .dimensions(25, 25)
+//!     .fishes(10)
+//!     .sharks(5)
+//!     .seed(42)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(board.count_animals(), (10, 5));
+//! ```
+use crate::{
+    parser::parse_layout, Board, ExtinctionPolicy, Neighbourhood, Result, SimulationConfig,
+    SimulationError, Topology, UpdateOrder,
+};
+
+/// Builds a [`Board`] from named parameters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BoardBuilder {
+    rows: u32,
+    columns: u32,
+    amount_fishes: u32,
+    amount_sharks: u32,
+    seed: Option<u64>,
+    config: SimulationConfig,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    update_order: UpdateOrder,
+    extinction_policy: ExtinctionPolicy,
+    lineage_log: bool,
+    layout: Option<String>,
+}
+
+impl BoardBuilder {
+    /// Sets the size of the board
+    ///
+    /// # Arguments
+    /// * `rows` - The amount of rows
+    /// * `columns` - The amount of columns
+    pub fn dimensions(mut self, rows: u32, columns: u32) -> Self {
+        self.rows = rows;
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the amount of fishes that are placed on the board initially
+    pub fn fishes(mut self, amount_fishes: u32) -> Self {
+        self.amount_fishes = amount_fishes;
+        self
+    }
+
+    /// Sets the amount of sharks that are placed on the board initially
+    pub fn sharks(mut self, amount_sharks: u32) -> Self {
+        self.amount_sharks = amount_sharks;
+        self
+    }
+
+    /// Sets the seed of the random number generator (see [`Board::with_seed`])
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the parameters of the simulation
+    pub fn config(mut self, config: SimulationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the amount of steps after which a fish breeds
+    pub fn fish_breed_time(mut self, fish_breed_time: u32) -> Self {
+        self.config.fish_breed_time = fish_breed_time;
+        self
+    }
+
+    /// Sets the amount of steps after which a shark breeds
+    pub fn shark_breed_time(mut self, shark_breed_time: u32) -> Self {
+        self.config.shark_breed_time = shark_breed_time;
+        self
+    }
+
+    /// Sets the amount of steps a shark survives without eating a fish
+    pub fn shark_starvation_time(mut self, shark_starvation_time: u32) -> Self {
+        self.config.shark_starvation_time = shark_starvation_time;
+        self
+    }
+
+    /// Sets which edges of the board wrap around
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets which fields around an animal it can move to or hunt on
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Sets the order in which the animals act during a step
+    pub fn update_order(mut self, update_order: UpdateOrder) -> Self {
+        self.update_order = update_order;
+        self
+    }
+
+    /// Sets whether the simulation stops when a species died out (see
+    /// [`Board::with_extinction_policy`])
+    pub fn extinction_policy(mut self, policy: ExtinctionPolicy) -> Self {
+        self.extinction_policy = policy;
+        self
+    }
+
+    /// Logs the birth and death of every animal (see [`Board::with_lineage_log`])
+    pub fn lineage_log(mut self) -> Self {
+        self.lineage_log = true;
+        self
+    }
+
+    /// Places the animals as drawn in a layout instead of randomly
+    ///
+    /// The layout is a grid in the plain-text board format without header lines. It sets the
+    /// dimensions and the animals of the board, so [`BoardBuilder::dimensions`],
+    /// [`BoardBuilder::fishes`] and [`BoardBuilder::sharks`] are ignored.
+    ///
+    /// # Arguments
+    /// * `layout` - Rows of `F` for a fish, `S` for a shark and `_` for plankton
+    pub fn layout(mut self, layout: &str) -> Self {
+        self.layout = Some(layout.into());
+        self
+    }
+
+    /// Returns the seed that was set with [`BoardBuilder::seed`]
+    #[cfg(feature = "scenario")]
+    pub(crate) fn configured_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns `true` if the animals are placed as drawn in a layout
+    pub(crate) fn has_layout(&self) -> bool {
+        self.layout.is_some()
+    }
+
+    /// Returns the amount of fields set with [`BoardBuilder::dimensions`], which may exceed a
+    /// `u32`
+    pub(crate) fn amount_fields(&self) -> u64 {
+        u64::from(self.rows) * u64::from(self.columns)
+    }
+
+    /// Creates the board and places the animals randomly or as drawn in the layout on it
+    ///
+    /// # Errors
+    /// * [`SimulationError::ParseError`] if the layout is invalid
+    /// * [`SimulationError::InvalidDimensions`] if the board has no rows or columns or the
+    ///   amount of fields does not fit into a `u32`
+    /// * [`SimulationError::Overpopulated`] if there are more animals than fields
+    /// * [`SimulationError::InvalidConfig`] if a parameter of the config is invalid
+    pub fn build(mut self) -> Result<Board> {
+        let layout = self.layout.as_deref().map(parse_layout).transpose()?;
+        if let Some(layout) = &layout {
+            self.rows = layout.rows;
+            self.columns = layout.columns;
+            self.amount_fishes = 0;
+            self.amount_sharks = 0;
+        }
+
+        let fields = self.rows.checked_mul(self.columns);
+        if self.rows == 0 || self.columns == 0 || fields.is_none() {
+            return Err(SimulationError::InvalidDimensions {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+
+        let animals = u64::from(self.amount_fishes) + u64::from(self.amount_sharks);
+        let fields = fields.map_or(0, u64::from);
+        if animals > fields {
+            return Err(SimulationError::Overpopulated { animals, fields });
+        }
+
+        let mut board = Board::new(
+            self.amount_fishes,
+            self.amount_sharks,
+            self.rows,
+            self.columns,
+        )
+        .with_config(self.config)?
+        .with_topology(self.topology)
+        .with_neighbourhood(self.neighbourhood)
+        .with_update_order(self.update_order)
+        .with_extinction_policy(self.extinction_policy);
+        if let Some(seed) = self.seed {
+            board = board.with_seed(seed);
+        }
+        if self.lineage_log {
+            board = board.with_lineage_log();
+        }
+        match layout {
+            Some(layout) => board.place_animals(&layout.animals),
+            None => board.generate_random_animals(),
+        }
+
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_zero_sized_board() {
+        let result = Board::builder().dimensions(0, 5).build();
+        assert_eq!(
+            result,
+            Err(SimulationError::InvalidDimensions {
+                rows: 0,
+                columns: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_overpopulated_board() {
+        let result = Board::builder()
+            .dimensions(3, 3)
+            .fishes(6)
+            .sharks(4)
+            .build();
+        assert_eq!(
+            result,
+            Err(SimulationError::Overpopulated {
+                animals: 10,
+                fields: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_population_does_not_overflow() {
+        let result = Board::builder()
+            .dimensions(2, 2)
+            .fishes(u32::MAX)
+            .sharks(1)
+            .build();
+        assert!(matches!(result, Err(SimulationError::Overpopulated { .. })));
+    }
+
+    #[test]
+    fn test_too_many_fields() {
+        let result = Board::builder()
+            .dimensions(u32::MAX, 2)
+            .fishes(u32::MAX)
+            .sharks(u32::MAX)
+            .build();
+        assert_eq!(
+            result,
+            Err(SimulationError::InvalidDimensions {
+                rows: u32::MAX,
+                columns: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = SimulationConfig {
+            fish_breed_time: 0,
+            ..SimulationConfig::default()
+        };
+        let result = Board::builder()
+            .dimensions(5, 5)
+            .fishes(1)
+            .config(config)
+            .build();
+        assert!(
+            matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "fish_breed_time")
+        );
+    }
+
+    #[test]
+    fn test_single_parameters() {
+        let board = Board::builder()
+            .dimensions(5, 5)
+            .fish_breed_time(2)
+            .shark_breed_time(6)
+            .shark_starvation_time(4)
+            .build()
+            .unwrap();
+        assert_eq!(board.config().fish_breed_time, 2);
+        assert_eq!(board.config().shark_breed_time, 6);
+        assert_eq!(board.config().shark_starvation_time, 4);
+    }
+
+    #[test]
+    fn test_builder_matches_seeded_board() {
+        let built = Board::builder()
+            .dimensions(8, 6)
+            .fishes(12)
+            .sharks(4)
+            .seed(3)
+            .build()
+            .unwrap();
+
+        let mut board = Board::new(12, 4, 8, 6).with_seed(3);
+        board.generate_random_animals();
+        assert_eq!(built, board);
+    }
+
+    #[test]
+    fn test_layout() {
+        let board = Board::builder()
+            .dimensions(10, 10)
+            .fishes(50)
+            .layout("F__\n_S_")
+            .topology(Topology::Box)
+            .build()
+            .unwrap();
+        assert_eq!((board.rows(), board.columns()), (2, 3));
+        assert_eq!(board.count_animals(), (1, 1));
+        assert_eq!(board.topology(), Topology::Box);
+
+        let result = Board::builder().layout("seed = 1\nF").build();
+        assert!(matches!(
+            result,
+            Err(SimulationError::ParseError { line: 1, .. })
+        ));
+    }
+}
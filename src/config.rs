@@ -0,0 +1,77 @@
+//! Contains the parameters that control the behaviour of the animals.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, SimulationConfig};
+//!
+//! // Sharks that starve after 4 steps without food
+//! let config = SimulationConfig {
+//!     shark_starvation_time: 4,
+//!     ..SimulationConfig::default()
+//! };
+//! let mut board = Board::new(10, 5, 25, 25).with_config(config).unwrap();
+//! board.generate_random_animals();
+//! ```
+use std::ops::Range;
+
+use crate::{Result, SimulationError};
+
+/// Parameters of the simulation
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationConfig {
+    /// The amount of steps after which a fish breeds
+    pub fish_breed_time: u32,
+    /// The amount of steps after which a shark breeds
+    pub shark_breed_time: u32,
+    /// The amount of steps a shark survives without eating a fish
+    pub shark_starvation_time: u32,
+    /// The range the initial life of a newly created shark is randomly chosen from
+    pub initial_shark_life: Range<u32>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            fish_breed_time: 3,
+            shark_breed_time: 8,
+            shark_starvation_time: 8,
+            initial_shark_life: 1..8,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Checks that all parameters can be used for a simulation
+    ///
+    /// # Errors
+    /// If a breed time or the starvation time is zero or the initial shark life range is empty or
+    /// contains zero
+    pub fn validate(&self) -> Result {
+        let positive = [
+            ("fish_breed_time", self.fish_breed_time),
+            ("shark_breed_time", self.shark_breed_time),
+            ("shark_starvation_time", self.shark_starvation_time),
+        ];
+        for (key, value) in positive {
+            if value == 0 {
+                return Err(SimulationError::InvalidConfig {
+                    key: key.into(),
+                    message: "must be greater than zero".into(),
+                });
+            }
+        }
+
+        if self.initial_shark_life.is_empty() || self.initial_shark_life.start == 0 {
+            return Err(SimulationError::InvalidConfig {
+                key: "initial_shark_life".into(),
+                message: format!(
+                    "{:?} must be a non-empty range of positive values",
+                    self.initial_shark_life
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Contains a writer that exports the population history of a board as CSV.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, CsvWriter};
+//!
+//! let mut board = Board::new(10, 5, 25, 25).with_seed(42);
+//! board.generate_random_animals();
+//!
+//! // Stream one row per step while the simulation is running
+//! let mut writer = CsvWriter::new(std::io::stdout()).unwrap();
+//! writer.write_report(board.statistics().last().unwrap()).unwrap();
+//! for _ in 0..10 {
+//!     let report = board.step().unwrap();
+//!     writer.write_report(&report).unwrap();
+//! }
+//! ```
+use std::io::{self, Write};
+
+use crate::{Statistics, StepReport};
+
+const HEADER: &str = "step,fishes,sharks,plankton,fish_births,shark_births,fishes_eaten,sharks_starved,fish_moves,shark_moves";
+
+/// Writes [`StepReport`]s as rows of a CSV file
+#[derive(Debug)]
+pub struct CsvWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Creates a new writer and writes the header row
+    ///
+    /// # Arguments
+    /// * `writer` - The destination of the CSV rows
+    ///
+    /// # Errors
+    /// If the header could not be written
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writeln!(writer, "{HEADER}")?;
+        Ok(CsvWriter { writer })
+    }
+
+    /// Writes the row of a single step
+    ///
+    /// # Errors
+    /// If the row could not be written
+    pub fn write_report(&mut self, report: &StepReport) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{},{},{},{},{}",
+            report.step,
+            report.fishes,
+            report.sharks,
+            report.plankton,
+            report.fish_births,
+            report.shark_births,
+            report.fishes_eaten,
+            report.sharks_starved,
+            report.fish_moves,
+            report.shark_moves
+        )
+    }
+
+    /// Writes the rows of all recorded steps
+    ///
+    /// # Errors
+    /// If a row could not be written
+    pub fn write_statistics(&mut self, statistics: &Statistics) -> io::Result<()> {
+        for report in statistics {
+            self.write_report(report)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the writer and returns it
+    ///
+    /// # Errors
+    /// If the writer could not be flushed
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Board;
+
+    #[test]
+    fn test_rows_match_statistics() {
+        let mut board = Board::new(20, 5, 8, 8).with_seed(2);
+        board.generate_random_animals();
+        for _ in 0..5 {
+            board.step().unwrap();
+        }
+
+        let mut writer = CsvWriter::new(Vec::new()).unwrap();
+        writer.write_statistics(board.statistics()).unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines[1], "0,20,5,39,0,0,0,0,0,0");
+        for (line, report) in lines[1..].iter().zip(board.statistics()) {
+            let columns: Vec<u32> = line.split(',').map(|c| c.parse().unwrap()).collect();
+            assert_eq!(columns.len(), 10);
+            assert_eq!(columns[0], report.step);
+            assert_eq!(columns[1] + columns[2] + columns[3], 64);
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Contains Monte-Carlo statistics over many seeded replicates of one board.
+//!
+//! Every replicate is simulated on its own board with the seed `seed + n`. The replicates are
+//! distributed over all CPU cores like the runs of a [`Sweep`](crate::Sweep). For every step
+//! the ensemble describes the distribution of the amounts of fishes and sharks over the
+//! replicates and the share of replicates in which a species already died out.
+//!
+//! A replicate that was stopped early by the extinction policy of the board keeps its last
+//! counts for the remaining steps.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, Ensemble};
+//!
+//! let ensemble = Ensemble::new(Board::builder().dimensions(20, 20).fishes(80).sharks(20))
+//!     .replicates(20)
+//!     .steps(50)
+//!     .quantiles([0.05, 0.5, 0.95])
+//!     .seed(42)
+//!     .run()
+//!     .unwrap();
+//!
+//! // The initial state and one entry per step
+//! assert_eq!(ensemble.steps().len(), 51);
+//! let last = ensemble.steps().last().unwrap();
+//! println!(
+//!     "{:.1} ± {:.1} sharks, 90% between {} and {}, extinct in {:.0}% of the runs",
+//!     last.shark_mean,
+//!     last.shark_std_dev,
+//!     last.shark_quantiles[0],
+//!     last.shark_quantiles[2],
+//!     last.sharks_extinct_probability * 100.0
+//! );
+//! ```
+use std::io::{self, Write};
+
+use crate::{sweep::run_parallel, BoardBuilder, Result, SimulationError};
+
+/// Simulates seeded replicates of a board
+#[derive(Clone, Debug)]
+pub struct Ensemble {
+    builder: BoardBuilder,
+    replicates: u32,
+    steps: u32,
+    quantiles: Vec<f64>,
+    seed: Option<u64>,
+    threads: Option<usize>,
+}
+
+/// The distribution of the populations over all replicates in one step
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnsembleStep {
+    /// The number of the step, 0 is the initial state
+    pub step: u32,
+    /// The mean amount of fishes
+    pub fish_mean: f64,
+    /// The sample standard deviation of the amount of fishes
+    pub fish_std_dev: f64,
+    /// The amount of fishes at every quantile of [`EnsembleStatistics::quantiles`]
+    pub fish_quantiles: Vec<f64>,
+    /// The mean amount of sharks
+    pub shark_mean: f64,
+    /// The sample standard deviation of the amount of sharks
+    pub shark_std_dev: f64,
+    /// The amount of sharks at every quantile of [`EnsembleStatistics::quantiles`]
+    pub shark_quantiles: Vec<f64>,
+    /// The share of replicates in which the fishes died out in this step or before
+    pub fish_extinct_probability: f64,
+    /// The share of replicates in which the sharks died out in this step or before
+    pub sharks_extinct_probability: f64,
+}
+
+/// The statistics of all replicates of an [`Ensemble`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnsembleStatistics {
+    replicates: u32,
+    quantiles: Vec<f64>,
+    steps: Vec<EnsembleStep>,
+}
+
+impl Ensemble {
+    /// Creates an ensemble of 100 replicates that are simulated for 100 steps
+    ///
+    /// The quantiles default to 5%, 25%, 50%, 75% and 95%.
+    ///
+    /// # Arguments
+    /// * `builder` - Describes the board of every replicate, its seed is replaced
+    pub fn new(builder: BoardBuilder) -> Self {
+        Ensemble {
+            builder,
+            replicates: 100,
+            steps: 100,
+            quantiles: vec![0.05, 0.25, 0.5, 0.75, 0.95],
+            seed: None,
+            threads: None,
+        }
+    }
+
+    /// Sets the amount of replicates
+    pub fn replicates(mut self, replicates: u32) -> Self {
+        self.replicates = replicates;
+        self
+    }
+
+    /// Sets the maximum amount of steps of every replicate
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the quantiles of the bands, e.g. `[0.05, 0.95]` for a 90% band
+    pub fn quantiles(mut self, quantiles: impl IntoIterator<Item = f64>) -> Self {
+        self.quantiles = quantiles.into_iter().collect();
+        self
+    }
+
+    /// Sets the seed of the first replicate, a random seed is used if it is not set
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the amount of threads, all CPU cores are used if it is not set
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Simulates all replicates
+    ///
+    /// # Errors
+    /// * [`SimulationError::InvalidConfig`] if there are no replicates or a quantile is not
+    ///   between 0 and 1
+    /// * The error of [`BoardBuilder::build`] if the board is invalid
+    pub fn run(&self) -> Result<EnsembleStatistics> {
+        if self.replicates == 0 {
+            return Err(SimulationError::InvalidConfig {
+                key: "replicates".into(),
+                message: "must be greater than zero".into(),
+            });
+        }
+        if let Some(quantile) = self
+            .quantiles
+            .iter()
+            .find(|quantile| !(0.0..=1.0).contains(*quantile))
+        {
+            return Err(SimulationError::InvalidConfig {
+                key: "quantiles".into(),
+                message: format!("{quantile} is not between 0 and 1"),
+            });
+        }
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let seeds: Vec<u64> = (0..self.replicates)
+            .map(|replicate| seed.wrapping_add(u64::from(replicate)))
+            .collect();
+        let histories = run_parallel(&seeds, self.threads, |seed| self.simulate(*seed))
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut fish_extinct = vec![false; histories.len()];
+        let mut sharks_extinct = vec![false; histories.len()];
+        let steps = (0..=self.steps)
+            .map(|step| {
+                // Replicates that stopped early keep their last counts
+                let at = |history: &Vec<u32>| history[(step as usize).min(history.len() - 1)];
+                let fishes: Vec<u32> = histories.iter().map(|(fishes, _)| at(fishes)).collect();
+                let sharks: Vec<u32> = histories.iter().map(|(_, sharks)| at(sharks)).collect();
+                let (fish_mean, fish_std_dev) = mean_and_std_dev(&fishes);
+                let (shark_mean, shark_std_dev) = mean_and_std_dev(&sharks);
+
+                EnsembleStep {
+                    step,
+                    fish_mean,
+                    fish_std_dev,
+                    fish_quantiles: quantiles(&fishes, &self.quantiles),
+                    shark_mean,
+                    shark_std_dev,
+                    shark_quantiles: quantiles(&sharks, &self.quantiles),
+                    fish_extinct_probability: extinct_share(&mut fish_extinct, &fishes),
+                    sharks_extinct_probability: extinct_share(&mut sharks_extinct, &sharks),
+                }
+            })
+            .collect();
+
+        Ok(EnsembleStatistics {
+            replicates: self.replicates,
+            quantiles: self.quantiles.clone(),
+            steps,
+        })
+    }
+
+    /// Returns the amounts of fishes and sharks of every step of a replicate
+    fn simulate(&self, seed: u64) -> Result<(Vec<u32>, Vec<u32>)> {
+        let mut board = self.builder.clone().seed(seed).build()?;
+        for _ in 0..self.steps {
+            if board.step().is_err() {
+                break;
+            }
+        }
+        let statistics = board.statistics();
+        Ok((statistics.fishes().collect(), statistics.sharks().collect()))
+    }
+}
+
+impl EnsembleStatistics {
+    /// Returns the amount of replicates
+    pub fn replicates(&self) -> u32 {
+        self.replicates
+    }
+
+    /// Returns the quantiles of the bands in the order of [`EnsembleStep::fish_quantiles`]
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+
+    /// Returns the statistics of every step starting with the initial state
+    pub fn steps(&self) -> &[EnsembleStep] {
+        &self.steps
+    }
+
+    /// Returns the statistics of a step
+    ///
+    /// # Arguments
+    /// * `step` - The number of the step, 0 is the initial state
+    pub fn get(&self, step: u32) -> Option<&EnsembleStep> {
+        self.steps.get(step as usize)
+    }
+
+    /// Writes one CSV row per step
+    ///
+    /// The quantile columns are named after their quantile, e.g. `fish_q0.5` for the median.
+    ///
+    /// # Errors
+    /// If a row could not be written
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let columns = |species: &str| {
+            self.quantiles
+                .iter()
+                .map(|quantile| format!(",{species}_q{quantile}"))
+                .collect::<String>()
+        };
+        writeln!(
+            writer,
+            "step,fish_mean,fish_std_dev{},shark_mean,shark_std_dev{},\
+             fish_extinct_probability,sharks_extinct_probability",
+            columns("fish"),
+            columns("shark")
+        )?;
+
+        let values = |values: &[f64]| {
+            values
+                .iter()
+                .map(|value| format!(",{value}"))
+                .collect::<String>()
+        };
+        for step in &self.steps {
+            writeln!(
+                writer,
+                "{},{},{}{},{},{}{},{},{}",
+                step.step,
+                step.fish_mean,
+                step.fish_std_dev,
+                values(&step.fish_quantiles),
+                step.shark_mean,
+                step.shark_std_dev,
+                values(&step.shark_quantiles),
+                step.fish_extinct_probability,
+                step.sharks_extinct_probability
+            )?;
+        }
+        writer.flush()
+    }
+}
+
+/// Returns the mean and the sample standard deviation of the values
+fn mean_and_std_dev(values: &[u32]) -> (f64, f64) {
+    let length = values.len() as f64;
+    let mean = values.iter().map(|value| f64::from(*value)).sum::<f64>() / length;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let squares: f64 = values
+        .iter()
+        .map(|value| (f64::from(*value) - mean).powi(2))
+        .sum();
+    (mean, (squares / (length - 1.0)).sqrt())
+}
+
+/// Returns the quantiles of the values, interpolated linearly between two values
+fn quantiles(values: &[u32], quantiles: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    quantiles
+        .iter()
+        .map(|quantile| {
+            let position = quantile * (sorted.len() - 1) as f64;
+            let lower = position.floor() as usize;
+            let upper = position.ceil() as usize;
+            let fraction = position - lower as f64;
+            f64::from(sorted[lower]) * (1.0 - fraction) + f64::from(sorted[upper]) * fraction
+        })
+        .collect()
+}
+
+/// Marks the replicates without animals and returns the share of replicates that were marked
+/// so far
+fn extinct_share(extinct: &mut [bool], counts: &[u32]) -> f64 {
+    for (extinct, count) in extinct.iter_mut().zip(counts) {
+        *extinct |= *count == 0;
+    }
+    extinct.iter().filter(|extinct| **extinct).count() as f64 / extinct.len() as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Board, ExtinctionPolicy};
+
+    #[test]
+    fn test_statistics_of_replicates() {
+        let builder = Board::builder()
+            .dimensions(6, 6)
+            .fishes(6)
+            .sharks(6)
+            .extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+        let ensemble = Ensemble::new(builder.clone())
+            .replicates(10)
+            .steps(40)
+            .quantiles([0.0, 0.5, 1.0])
+            .seed(5)
+            .threads(4);
+        let statistics = ensemble.run().unwrap();
+
+        assert_eq!(statistics.replicates(), 10);
+        assert_eq!(statistics.steps().len(), 41);
+        let initial = statistics.get(0).unwrap();
+        assert_eq!(initial.fish_mean, 6.0);
+        assert_eq!(initial.fish_std_dev, 0.0);
+        assert_eq!(initial.shark_quantiles, vec![6.0, 6.0, 6.0]);
+        assert_eq!(initial.fish_extinct_probability, 0.0);
+
+        // The extinction probability never decreases
+        for pair in statistics.steps().windows(2) {
+            assert!(pair[1].fish_extinct_probability >= pair[0].fish_extinct_probability);
+            assert!(pair[1].sharks_extinct_probability >= pair[0].sharks_extinct_probability);
+        }
+
+        // The last step agrees with the replicates
+        let last = statistics.get(40).unwrap();
+        let mut sharks = vec![];
+        for seed in 5..15 {
+            let mut board = builder.clone().seed(seed).build().unwrap();
+            for _ in 0..40 {
+                if board.step().is_err() {
+                    break;
+                }
+            }
+            sharks.push(board.count_animals().1);
+        }
+        let extinct = sharks.iter().filter(|sharks| **sharks == 0).count();
+        assert_eq!(last.sharks_extinct_probability, extinct as f64 / 10.0);
+        assert_eq!(
+            last.shark_quantiles[0],
+            f64::from(*sharks.iter().min().unwrap())
+        );
+        assert_eq!(
+            last.shark_quantiles[2],
+            f64::from(*sharks.iter().max().unwrap())
+        );
+
+        assert_eq!(statistics, ensemble.threads(1).run().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let builder = Board::builder().dimensions(3, 3).fishes(1);
+        let result = Ensemble::new(builder.clone()).replicates(0).run();
+        assert!(
+            matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "replicates")
+        );
+        let result = Ensemble::new(builder).quantiles([0.5, 1.5]).run();
+        assert!(
+            matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "quantiles")
+        );
+    }
+
+    #[test]
+    fn test_quantiles_and_std_dev() {
+        assert_eq!(
+            quantiles(&[4, 1, 3, 2], &[0.0, 0.5, 1.0]),
+            vec![1.0, 2.5, 4.0]
+        );
+        assert_eq!(mean_and_std_dev(&[2, 4]), (3.0, 2f64.sqrt()));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let statistics = Ensemble::new(Board::builder().dimensions(4, 4).fishes(4).sharks(1))
+            .replicates(3)
+            .steps(2)
+            .quantiles([0.5])
+            .seed(1)
+            .run()
+            .unwrap();
+        let mut csv = vec![];
+        statistics.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "step,fish_mean,fish_std_dev,fish_q0.5,shark_mean,shark_std_dev,shark_q0.5,\
+                 fish_extinct_probability,sharks_extinct_probability"
+            )
+        );
+        assert_eq!(lines.next(), Some("0,4,0,4,1,0,1,0,0"));
+        assert_eq!(lines.count(), 2);
+    }
+}
@@ -4,35 +4,130 @@ use log::{debug, info};
 use rand::Rng;
 use std::fmt;
 
-type Position = (u32, u32);
-type NeighbourFields = (Position, Position, Position, Position);
+use crate::{Neighbourhood, Result, SimulationConfig, SimulationError, Topology};
 
-const MAX_SHARK_LIFETIME: u32 = 8;
-const SHARK_BREED_TIME: u32 = 8;
-const FISH_BREED_TIME: u32 = 3;
+type Position = (u32, u32);
 
+/// The state of an animal that changes from step to step
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnimalStatus {
+    id: u64,
+    parent_id: Option<u64>,
     life: Option<u32>,
     breed_counter: u32,
+    age: u32,
 }
 
 impl AnimalStatus {
-    fn new_fish() -> Self {
+    /// Creates the status of a fish
+    ///
+    /// The fish gets an id when it is placed on a board.
+    ///
+    /// # Arguments
+    /// * `turns_until_breeding` - The amount of steps until the fish breeds
+    pub fn fish(turns_until_breeding: u32) -> Self {
         AnimalStatus {
+            id: 0,
+            parent_id: None,
             life: None,
-            breed_counter: FISH_BREED_TIME,
+            breed_counter: turns_until_breeding,
+            age: 0,
         }
     }
 
-    fn new_shark() -> Self {
-        let mut rng = rand::thread_rng();
+    /// Creates the status of a shark
+    ///
+    /// The shark gets an id when it is placed on a board.
+    ///
+    /// # Arguments
+    /// * `remaining_life` - The amount of steps the shark survives without eating a fish
+    /// * `turns_until_breeding` - The amount of steps until the shark breeds
+    ///
+    /// # Errors
+    /// [`SimulationError::SharkWithoutLife`] if the remaining life is zero because the shark
+    /// would already be dead
+    pub fn shark(remaining_life: u32, turns_until_breeding: u32) -> Result<Self> {
+        if remaining_life == 0 {
+            return Err(SimulationError::SharkWithoutLife);
+        }
+
+        Ok(AnimalStatus {
+            id: 0,
+            parent_id: None,
+            life: Some(remaining_life),
+            breed_counter: turns_until_breeding,
+            age: 0,
+        })
+    }
+
+    /// Sets the amount of steps the animal has already lived
+    ///
+    /// # Arguments
+    /// * `age` - The age of the animal
+    pub fn with_age(mut self, age: u32) -> Self {
+        self.age = age;
+        self
+    }
+
+    /// Returns the id that identifies the animal on its board
+    ///
+    /// Ids start at 1 and are assigned when an animal is placed on a board or born. A status
+    /// that was not placed on a board yet has the id 0.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the id of the animal this animal was born from or `None` if it was placed on the
+    /// board
+    pub fn parent_id(&self) -> Option<u64> {
+        self.parent_id
+    }
+
+    pub(crate) fn assign_id(&mut self, id: u64, parent_id: Option<u64>) {
+        self.id = id;
+        self.parent_id = parent_id;
+    }
+
+    /// Returns the amount of steps a shark survives without eating a fish or `None` for a fish
+    pub fn remaining_life(&self) -> Option<u32> {
+        self.life
+    }
+
+    /// Returns the amount of steps until the animal breeds
+    ///
+    /// The animal breeds at the end of the step in which this counter reaches zero if it
+    /// moves. A value of zero means that the breed time was reached in the last step and the
+    /// counter starts again with the next step.
+    pub fn turns_until_breeding(&self) -> u32 {
+        self.breed_counter
+    }
+
+    /// Returns the amount of steps the animal has lived
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    fn new_fish(config: &SimulationConfig) -> Self {
+        Self::fish(config.fish_breed_time)
+    }
+
+    fn new_shark<R: Rng + ?Sized>(config: &SimulationConfig, rng: &mut R) -> Self {
         AnimalStatus {
-            life: Some(rng.gen_range(1..SHARK_BREED_TIME)),
-            breed_counter: SHARK_BREED_TIME,
+            id: 0,
+            parent_id: None,
+            life: Some(rng.gen_range(config.initial_shark_life.clone())),
+            breed_counter: config.shark_breed_time,
+            age: 0,
         }
     }
 
+    /// Advances the breed counter and the age by one step
+    fn grow_older(&mut self) {
+        self.reduce_breet();
+        self.age += 1;
+    }
+
     fn reduce_breet(&mut self) {
         self.breed_counter -= 1;
     }
@@ -43,20 +138,24 @@ impl AnimalStatus {
         }
     }
 
-    fn reset_life(&mut self) {
+    fn reset_life(&mut self, config: &SimulationConfig) {
         if self.life.is_some() {
-            self.life = Some(MAX_SHARK_LIFETIME)
+            self.life = Some(config.shark_starvation_time)
         }
     }
 
-    fn reset_breed(&mut self, r#type: &FieldType) {
+    fn reset_breed(&mut self, r#type: &FieldType, config: &SimulationConfig) {
         match r#type {
-            FieldType::Fish => self.breed_counter = FISH_BREED_TIME,
-            FieldType::Shark => self.breed_counter = SHARK_BREED_TIME,
+            FieldType::Fish => self.breed_counter = config.fish_breed_time,
+            FieldType::Shark => self.breed_counter = config.shark_breed_time,
             _ => (),
         }
     }
 
+    pub(crate) fn is_shark(&self) -> bool {
+        self.life.is_some()
+    }
+
     fn is_dead(&self) -> bool {
         if let Some(life) = self.life {
             return life == 0;
@@ -65,6 +164,7 @@ impl AnimalStatus {
         false
     }
 
+    /// Returns `true` if the breed time was reached in the last step
     pub fn has_to_breed(&self) -> bool {
         self.breed_counter == 0
     }
@@ -72,18 +172,27 @@ impl AnimalStatus {
 
 /// Represents a type of a field
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FieldType {
+    /// A shark that eats fishes and starves without them
     Shark,
+    /// A fish that moves around and breeds
     Fish,
+    /// An empty field
     Plankton,
 }
 
 /// Represents a field on the board
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
+    /// What is on the field
     pub r#type: FieldType,
+    /// The column of the field
     pub x: u32,
+    /// The row of the field
     pub y: u32,
+    /// The status of the animal on the field or `None` for plankton
     pub status: Option<AnimalStatus>,
 }
 
@@ -94,35 +203,52 @@ impl Field {
     /// * `type` - The type of the field
     /// * `x` - The x coordinate of the field
     /// * `y` - The y coordinate of the field
+    /// * `status` - The status of the animal on the field or `None` for plankton
+    ///
+    /// # Panics
+    /// If the status is `None` for a fish or a shark. A freshly born animal is created with
+    /// [`Field::spawn`] instead, so its status comes from the config and random number
+    /// generator of the simulation.
     pub fn new(r#type: FieldType, x: u32, y: u32, status: Option<AnimalStatus>) -> Field {
-        if let Some(status) = status {
-            return Field {
-                r#type,
-                x,
-                y,
-                status: Some(status),
-            };
+        assert!(
+            status.is_some() || r#type == FieldType::Plankton,
+            "A {type:?} needs a status, use Field::spawn for a freshly born animal"
+        );
+
+        Field {
+            r#type,
+            x,
+            y,
+            status,
         }
+    }
 
-        match r#type {
-            FieldType::Fish => Field {
-                r#type,
-                x,
-                y,
-                status: Some(AnimalStatus::new_fish()),
-            },
-            FieldType::Shark => Field {
-                r#type,
-                x,
-                y,
-                status: Some(AnimalStatus::new_shark()),
-            },
-            FieldType::Plankton => Field {
-                r#type,
-                x,
-                y,
-                status: None,
-            },
+    /// Creates a new field with a freshly born animal of the given type
+    ///
+    /// # Arguments
+    /// * `type` - The type of the field
+    /// * `x` - The x coordinate of the field
+    /// * `y` - The y coordinate of the field
+    /// * `config` - The parameters of the simulation
+    /// * `rng` - The random number generator used for the initial status
+    pub fn spawn<R: Rng + ?Sized>(
+        r#type: FieldType,
+        x: u32,
+        y: u32,
+        config: &SimulationConfig,
+        rng: &mut R,
+    ) -> Field {
+        let status = match r#type {
+            FieldType::Fish => Some(AnimalStatus::new_fish(config)),
+            FieldType::Shark => Some(AnimalStatus::new_shark(config, rng)),
+            FieldType::Plankton => None,
+        };
+
+        Field {
+            r#type,
+            x,
+            y,
+            status,
         }
     }
 
@@ -130,63 +256,87 @@ impl Field {
     ///
     /// # Arguments
     /// * `animals` - The board before the step
+    /// * `config` - The parameters of the simulation
+    /// * `topology` - Describes which edges of the board wrap around
+    /// * `neighbourhood` - Describes which fields around are neighbours
+    /// * `rng` - The random number generator used to select a move
     ///
     /// # Returns
     /// The new position for the field
-    pub fn step(&self, animals: &[Vec<Field>]) -> Option<(Position, Option<AnimalStatus>)> {
+    pub(crate) fn step<R: Rng + ?Sized>(
+        &self,
+        animals: &[Vec<Field>],
+        config: &SimulationConfig,
+        topology: Topology,
+        neighbourhood: Neighbourhood,
+        rng: &mut R,
+    ) -> Option<(Position, Option<AnimalStatus>)> {
+        let neighbours = self.get_positions_around(animals, topology, neighbourhood);
         match self.r#type {
-            FieldType::Fish => Some(self.get_next_fish_position(animals)),
+            FieldType::Fish => Some(self.get_next_fish_position(animals, neighbours, config, rng)),
             FieldType::Shark => self
-                .get_next_shark_position(animals)
+                .get_next_shark_position(animals, neighbours, config, rng)
                 .map(|((x, y), state)| ((x, y), Some(state))),
             _ => Some(((self.x, self.y), None)),
         }
     }
 
-    fn get_positions_around(&self, animals: &[Vec<Field>]) -> NeighbourFields {
-        let up = (
-            self.x % (animals.first().unwrap().len() as u32),
-            ((self.y + (animals.len() as u32) - 1) % (animals.len() as u32)),
-        );
-        let down = (
-            self.x % (animals.first().unwrap().len() as u32),
-            ((self.y + (animals.len() as u32) + 1) % (animals.len() as u32)),
-        );
-        let left = (
-            ((self.x + (animals.first().unwrap().len() as u32) - 1) % (animals.len() as u32)),
-            self.y % (animals.len() as u32),
-        );
-        let right = (
-            ((self.x + (animals.first().unwrap().len() as u32) + 1) % (animals.len() as u32)),
-            self.y % (animals.len() as u32),
-        );
+    /// Returns the status of the animal if it neither moves nor eats during a step
+    ///
+    /// # Arguments
+    /// * `config` - The parameters of the simulation
+    ///
+    /// # Returns
+    /// The new status or `None` if the animal starved
+    pub(crate) fn stay(&self, config: &SimulationConfig) -> Option<AnimalStatus> {
+        let mut new_status = self.status.clone()?;
+        if new_status.has_to_breed() {
+            new_status.reset_breed(&self.r#type, config);
+        }
+        new_status.grow_older();
+
+        new_status.reduce_life();
+        if new_status.is_dead() {
+            debug!("Shark ({}, {}) is dead", self.x, self.y);
+            return None;
+        }
+
+        Some(new_status)
+    }
 
-        (up, down, left, right)
+    fn get_positions_around(
+        &self,
+        animals: &[Vec<Field>],
+        topology: Topology,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Position> {
+        let rows = animals.len() as u32;
+        let columns = animals.first().unwrap().len() as u32;
+
+        neighbourhood.positions((self.x, self.y), topology, rows, columns)
     }
 
-    fn get_next_fish_position(&self, animals: &[Vec<Field>]) -> (Position, Option<AnimalStatus>) {
+    fn get_next_fish_position<R: Rng + ?Sized>(
+        &self,
+        animals: &[Vec<Field>],
+        neighbours: impl Iterator<Item = Position>,
+        config: &SimulationConfig,
+        rng: &mut R,
+    ) -> (Position, Option<AnimalStatus>) {
         let mut new_status = self.status.clone().unwrap();
         if new_status.has_to_breed() {
-            new_status.reset_breed(&self.r#type);
+            new_status.reset_breed(&self.r#type, config);
         }
 
         let mut possible_moves: Vec<Position> = vec![];
 
-        let (up, down, left, right) = self.get_positions_around(animals);
-        if animals[up.1 as usize][up.0 as usize].check_field_empty() {
-            possible_moves.push(up);
-        }
-        if animals[down.1 as usize][down.0 as usize].check_field_empty() {
-            possible_moves.push(down);
-        }
-        if animals[left.1 as usize][left.0 as usize].check_field_empty() {
-            possible_moves.push(left);
-        }
-        if animals[right.1 as usize][right.0 as usize].check_field_empty() {
-            possible_moves.push(right);
+        for neighbour in neighbours {
+            if animals[neighbour.1 as usize][neighbour.0 as usize].check_field_empty() {
+                possible_moves.push(neighbour);
+            }
         }
 
-        new_status.reduce_breet();
+        new_status.grow_older();
         debug!(
             "Reduced breed counter for fish: {:?} old=({:?})",
             new_status, self.status
@@ -197,7 +347,6 @@ impl Field {
         }
 
         // Select random move
-        let mut rng = rand::thread_rng();
         let move_index = rng.gen_range(0..possible_moves.len());
         info!(
             "Fish ({}, {}) moves to ({}, {})",
@@ -206,12 +355,18 @@ impl Field {
         (possible_moves[move_index], Some(new_status))
     }
 
-    fn get_next_shark_position(&self, animals: &[Vec<Field>]) -> Option<(Position, AnimalStatus)> {
+    fn get_next_shark_position<R: Rng + ?Sized>(
+        &self,
+        animals: &[Vec<Field>],
+        neighbours: impl Iterator<Item = Position>,
+        config: &SimulationConfig,
+        rng: &mut R,
+    ) -> Option<(Position, AnimalStatus)> {
         let mut new_status = self.status.clone().unwrap();
         if new_status.has_to_breed() {
-            new_status.reset_breed(&self.r#type);
+            new_status.reset_breed(&self.r#type, config);
         }
-        new_status.reduce_breet();
+        new_status.grow_older();
         debug!(
             "Reduced breed counter for shark: {:?} old=({:?})",
             new_status.breed_counter, self.status
@@ -219,39 +374,23 @@ impl Field {
 
         let mut prioritized_moves: Vec<Position> = vec![];
         let mut possible_moves: Vec<Position> = vec![];
-        let (up, down, left, right) = self.get_positions_around(animals);
 
-        // Check if there is a fish in the neighbour fields
-        if animals[up.1 as usize][up.0 as usize].check_field_for_type(FieldType::Fish) {
-            prioritized_moves.push(up);
-        }
-        if animals[down.1 as usize][down.0 as usize].check_field_for_type(FieldType::Fish) {
-            prioritized_moves.push(down);
-        }
-        if animals[left.1 as usize][left.0 as usize].check_field_for_type(FieldType::Fish) {
-            prioritized_moves.push(left);
-        }
-        if animals[right.1 as usize][right.0 as usize].check_field_for_type(FieldType::Fish) {
-            prioritized_moves.push(right);
-        }
-        // Check for free fields around
-        if animals[up.1 as usize][up.0 as usize].check_field_empty() {
-            possible_moves.push(up);
-        }
-        if animals[down.1 as usize][down.0 as usize].check_field_empty() {
-            possible_moves.push(down);
-        }
-        if animals[left.1 as usize][left.0 as usize].check_field_empty() {
-            possible_moves.push(left);
-        }
-        if animals[right.1 as usize][right.0 as usize].check_field_empty() {
-            possible_moves.push(right);
+        for neighbour in neighbours {
+            let field = &animals[neighbour.1 as usize][neighbour.0 as usize];
+            // Check if there is a fish in the neighbour fields
+            if field.check_field_for_type(FieldType::Fish) {
+                prioritized_moves.push(neighbour);
+            }
+            // Check for free fields around
+            if field.check_field_empty() {
+                possible_moves.push(neighbour);
+            }
         }
 
         // If prioritized_moves is not empty then select a random move from it
         if !prioritized_moves.is_empty() {
-            let index = rand::thread_rng().gen_range(0..prioritized_moves.len());
-            new_status.reset_life();
+            let index = rng.gen_range(0..prioritized_moves.len());
+            new_status.reset_life(config);
             info!(
                 "Shark ({}, {}) moves to prio field {:?}",
                 self.x, self.y, prioritized_moves[index]
@@ -275,7 +414,7 @@ impl Field {
         }
 
         // select a random move from possible_moves
-        let index = rand::thread_rng().gen_range(0..possible_moves.len());
+        let index = rng.gen_range(0..possible_moves.len());
         info!(
             "Shark ({}, {}) moves to {:?}",
             self.x, self.y, possible_moves[index]
@@ -313,12 +452,17 @@ impl fmt::Display for Field {
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     fn create_empty_board() -> Vec<Vec<Field>> {
+        create_board(4, 4)
+    }
+
+    fn create_board(rows: u32, columns: u32) -> Vec<Vec<Field>> {
         let mut board: Vec<Vec<Field>> = vec![];
-        for y in 0..4 {
+        for y in 0..rows {
             let mut row: Vec<Field> = vec![];
-            for x in 0..4 {
+            for x in 0..columns {
                 row.push(Field::new(FieldType::Plankton, x, y, None));
             }
             board.push(row);
@@ -326,43 +470,241 @@ mod test {
         board
     }
 
+    fn fish(x: u32, y: u32) -> Field {
+        Field::new(FieldType::Fish, x, y, Some(AnimalStatus::fish(3)))
+    }
+
+    fn neighbours(field: &Field, board: &[Vec<Field>], topology: Topology) -> Vec<Position> {
+        field
+            .get_positions_around(board, topology, Neighbourhood::VonNeumann)
+            .collect()
+    }
+
     #[test]
     fn test_fish_in_middle() {
         let mut board = create_empty_board();
-        let fish = Field::new(FieldType::Fish, 2, 2, None);
+        let fish = fish(2, 2);
         board[2][2] = fish.clone();
 
-        let possible_moves = fish.get_positions_around(&board);
-        let expected_moves: NeighbourFields = ((2, 1), (2, 3), (1, 2), (3, 2));
+        let possible_moves = neighbours(&fish, &board, Topology::Torus);
+        let expected_moves: Vec<Position> = vec![(2, 1), (2, 3), (1, 2), (3, 2)];
         assert_eq!(expected_moves, possible_moves);
     }
 
     #[test]
     fn test_fish_in_corners() {
         let mut board = create_empty_board();
-        let upper_left = Field::new(FieldType::Fish, 0, 0, None);
-        let upper_right = Field::new(FieldType::Fish, 3, 0, None);
-        let lower_left = Field::new(FieldType::Fish, 0, 3, None);
-        let lower_right = Field::new(FieldType::Fish, 3, 3, None);
+        let upper_left = fish(0, 0);
+        let upper_right = fish(3, 0);
+        let lower_left = fish(0, 3);
+        let lower_right = fish(3, 3);
         board[0][0] = upper_left.clone();
         board[3][0] = upper_right.clone();
         board[0][3] = lower_left.clone();
         board[3][3] = lower_right.clone();
 
-        let possible_moves_upper_left = upper_left.get_positions_around(&board);
-        let expected_moves: NeighbourFields = ((0, 3), (0, 1), (3, 0), (1, 0));
+        let possible_moves_upper_left = neighbours(&upper_left, &board, Topology::Torus);
+        let expected_moves: Vec<Position> = vec![(0, 3), (0, 1), (3, 0), (1, 0)];
         assert_eq!(expected_moves, possible_moves_upper_left);
 
-        let possible_moves_upper_right = upper_right.get_positions_around(&board);
-        let expected_moves: NeighbourFields = ((3, 3), (3, 1), (2, 0), (0, 0));
+        let possible_moves_upper_right = neighbours(&upper_right, &board, Topology::Torus);
+        let expected_moves: Vec<Position> = vec![(3, 3), (3, 1), (2, 0), (0, 0)];
         assert_eq!(expected_moves, possible_moves_upper_right);
 
-        let possible_moves_lower_left = lower_left.get_positions_around(&board);
-        let expected_moves: NeighbourFields = ((0, 2), (0, 0), (3, 3), (1, 3));
+        let possible_moves_lower_left = neighbours(&lower_left, &board, Topology::Torus);
+        let expected_moves: Vec<Position> = vec![(0, 2), (0, 0), (3, 3), (1, 3)];
         assert_eq!(expected_moves, possible_moves_lower_left);
 
-        let possible_moves_lower_right = lower_right.get_positions_around(&board);
-        let expected_moves: NeighbourFields = ((3, 2), (3, 0), (2, 3), (0, 3));
+        let possible_moves_lower_right = neighbours(&lower_right, &board, Topology::Torus);
+        let expected_moves: Vec<Position> = vec![(3, 2), (3, 0), (2, 3), (0, 3)];
         assert_eq!(expected_moves, possible_moves_lower_right);
     }
+
+    #[test]
+    fn test_non_square_board_edges() {
+        let board = create_board(3, 5);
+        let right_edge = fish(4, 1);
+        let expected_moves: Vec<Position> = vec![(4, 0), (4, 2), (3, 1), (0, 1)];
+        assert_eq!(
+            expected_moves,
+            neighbours(&right_edge, &board, Topology::Torus)
+        );
+
+        let lower_left = fish(0, 2);
+        let expected_moves: Vec<Position> = vec![(0, 1), (0, 0), (4, 2), (1, 2)];
+        assert_eq!(
+            expected_moves,
+            neighbours(&lower_left, &board, Topology::Torus)
+        );
+    }
+
+    #[test]
+    fn test_box_corner() {
+        let board = create_empty_board();
+        let upper_left = fish(0, 0);
+        let expected_moves: Vec<Position> = vec![(0, 1), (1, 0)];
+        assert_eq!(
+            expected_moves,
+            neighbours(&upper_left, &board, Topology::Box)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_neighbours_are_adjacent_on_torus(
+            rows in 1u32..40,
+            columns in 1u32..40,
+            x in any::<u32>(),
+            y in any::<u32>(),
+        ) {
+            let (x, y) = (x % columns, y % rows);
+            let board = create_board(rows, columns);
+            let field = fish(x, y);
+            let neighbours = neighbours(&field, &board, Topology::Torus);
+            prop_assert_eq!(neighbours.len(), 4);
+
+            let (up, down, left, right) = (neighbours[0], neighbours[1], neighbours[2], neighbours[3]);
+            for (neighbour_x, neighbour_y) in [up, down, left, right] {
+                prop_assert!(neighbour_x < columns);
+                prop_assert!(neighbour_y < rows);
+            }
+
+            prop_assert_eq!(up.0, x);
+            prop_assert_eq!(down.0, x);
+            prop_assert_eq!(left.1, y);
+            prop_assert_eq!(right.1, y);
+            prop_assert_eq!((up.1 + 1) % rows, y);
+            prop_assert_eq!((y + 1) % rows, down.1);
+            prop_assert_eq!((left.0 + 1) % columns, x);
+            prop_assert_eq!((x + 1) % columns, right.0);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_all_neighbourhoods_stay_adjacent(
+            rows in 1u32..40,
+            columns in 1u32..40,
+            x in any::<u32>(),
+            y in any::<u32>(),
+        ) {
+            let (x, y) = (x % columns, y % rows);
+            let board = create_board(rows, columns);
+            let field = fish(x, y);
+
+            for neighbourhood in [
+                Neighbourhood::VonNeumann,
+                Neighbourhood::Moore,
+                Neighbourhood::Hexagonal,
+            ] {
+                let neighbours: Vec<Position> = field
+                    .get_positions_around(&board, Topology::Torus, neighbourhood)
+                    .collect();
+                prop_assert_eq!(neighbours.len(), neighbourhood.offsets(y).len());
+
+                for (neighbour_x, neighbour_y) in neighbours {
+                    prop_assert!(neighbour_x < columns);
+                    prop_assert!(neighbour_y < rows);
+                    let distance_x = (neighbour_x + columns - x) % columns;
+                    let distance_y = (neighbour_y + rows - y) % rows;
+                    prop_assert!(distance_x <= 1 || distance_x == columns - 1);
+                    prop_assert!(distance_y <= 1 || distance_y == rows - 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_controls_shark_life_and_breeding() {
+        let config = SimulationConfig {
+            fish_breed_time: 1,
+            shark_breed_time: 5,
+            shark_starvation_time: 5,
+            initial_shark_life: 2..3,
+        };
+        let mut rng = rand::thread_rng();
+        let mut board = create_empty_board();
+        let shark = Field::spawn(FieldType::Shark, 1, 1, &config, &mut rng);
+        assert_eq!(shark.status.as_ref().unwrap().life, Some(2));
+        let fish = Field::spawn(FieldType::Fish, 1, 2, &config, &mut rng);
+        board[1][1] = shark.clone();
+        board[2][1] = fish.clone();
+
+        let (_, fish_status) = fish
+            .step(
+                &board,
+                &config,
+                Topology::Torus,
+                Neighbourhood::VonNeumann,
+                &mut rng,
+            )
+            .unwrap();
+        assert!(fish_status.unwrap().has_to_breed());
+
+        let (position, shark_status) = shark
+            .step(
+                &board,
+                &config,
+                Topology::Torus,
+                Neighbourhood::VonNeumann,
+                &mut rng,
+            )
+            .unwrap();
+        let shark_status = shark_status.unwrap();
+        assert_eq!(position, (1, 2));
+        assert_eq!(shark_status.life, Some(5));
+        assert_eq!(shark_status.breed_counter, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "needs a status")]
+    fn test_new_animal_needs_status() {
+        Field::new(FieldType::Shark, 1, 1, None);
+    }
+
+    #[test]
+    fn test_status_constructors_and_getters() {
+        let fish = AnimalStatus::fish(2).with_age(4);
+        assert_eq!(fish.remaining_life(), None);
+        assert_eq!(fish.turns_until_breeding(), 2);
+        assert_eq!(fish.age(), 4);
+
+        let shark = AnimalStatus::shark(3, 1).unwrap();
+        assert_eq!(shark.remaining_life(), Some(3));
+        assert_eq!(shark.turns_until_breeding(), 1);
+        assert_eq!(shark.age(), 0);
+        assert_eq!(
+            AnimalStatus::shark(0, 1),
+            Err(SimulationError::SharkWithoutLife)
+        );
+    }
+
+    #[test]
+    fn test_animals_grow_older() {
+        let config = SimulationConfig::default();
+        let mut rng = rand::thread_rng();
+        let mut board = create_empty_board();
+        let shark = Field::new(
+            FieldType::Shark,
+            1,
+            1,
+            Some(AnimalStatus::shark(4, 3).unwrap().with_age(7)),
+        );
+        board[1][1] = shark.clone();
+
+        let (_, status) = shark
+            .step(
+                &board,
+                &config,
+                Topology::Torus,
+                Neighbourhood::VonNeumann,
+                &mut rng,
+            )
+            .unwrap();
+        let status = status.unwrap();
+        assert_eq!(status.age(), 8);
+        assert_eq!(status.remaining_life(), Some(3));
+        assert_eq!(status.turns_until_breeding(), 2);
+        assert_eq!(shark.stay(&config).unwrap().age(), 8);
+    }
 }
@@ -1,19 +1,163 @@
 mod board;
+mod builder;
+mod config;
+mod csv;
+mod ensemble;
 mod field;
-use std::fmt;
+mod lineage;
+mod matlab;
+mod neighbourhood;
+mod parser;
+mod report;
+#[cfg(feature = "scenario")]
+mod scenario;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod statistics;
+mod sweep;
+mod topology;
+#[cfg(feature = "tui")]
+mod tui;
+mod update;
+use std::{fmt, io};
 
 pub use board::Board;
+pub use builder::BoardBuilder;
+pub use config::SimulationConfig;
+pub use csv::CsvWriter;
+pub use ensemble::{Ensemble, EnsembleStatistics, EnsembleStep};
+pub use field::{AnimalStatus, Field, FieldType};
+pub use lineage::{Lineage, LineageRecord};
+pub use matlab::MatlabScript;
+pub use neighbourhood::Neighbourhood;
+pub use report::{ExtinctionPolicy, SimulationEvent, StepReport};
+#[cfg(feature = "scenario")]
+pub use scenario::{Output, OutputFormat, Scenario};
+#[cfg(feature = "serde")]
+pub use snapshot::SNAPSHOT_VERSION;
+pub use statistics::{AgeDistribution, Statistics};
+pub use sweep::{Sweep, SweepParameters, SweepRun, SweepSummary, SweepTable};
+pub use topology::Topology;
+#[cfg(feature = "tui")]
+pub use tui::Viewer;
+pub use update::UpdateOrder;
 
 /// Result type that is used by the library
-pub type Result = std::result::Result<(), SimulationError>;
+pub type Result<T = ()> = std::result::Result<T, SimulationError>;
 
 /// An error that can occur during the simulation
 #[derive(Debug, PartialEq, Eq)]
-pub struct SimulationError(String);
+pub enum SimulationError {
+    /// There are no fishes left on the board
+    FishExtinct {
+        /// The step in which the extinction was detected
+        step: u32,
+        /// The amount of sharks that are still alive
+        sharks: u32,
+    },
+    /// There are no sharks left on the board
+    SharksExtinct {
+        /// The step in which the extinction was detected
+        step: u32,
+        /// The amount of fishes that are still alive
+        fishes: u32,
+    },
+    /// The board has no fields because one of its dimensions is zero
+    InvalidDimensions { rows: u32, columns: u32 },
+    /// More animals should be placed than the board has fields
+    Overpopulated { animals: u64, fields: u64 },
+    /// A position lies outside of the board
+    OutOfBounds {
+        x: u32,
+        y: u32,
+        rows: u32,
+        columns: u32,
+    },
+    /// The status of an animal does not match its type
+    InvalidStatus { x: u32, y: u32, r#type: FieldType },
+    /// A shark should be created without any remaining life
+    SharkWithoutLife,
+    /// A parameter of the simulation has an invalid value
+    InvalidConfig { key: String, message: String },
+    /// The input could not be parsed
+    ParseError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Reading or writing failed
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    /// A snapshot was written by an incompatible version of the library
+    UnsupportedSnapshotVersion { version: u32 },
+    /// A snapshot is malformed or describes an inconsistent board
+    InvalidSnapshot { message: String },
+}
 
 impl fmt::Display for SimulationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            SimulationError::FishExtinct { step, sharks } => write!(
+                f,
+                "No fishes left on the board in step {step} ({sharks} sharks alive)"
+            ),
+            SimulationError::SharksExtinct { step, fishes } => write!(
+                f,
+                "No sharks left on the board in step {step} ({fishes} fishes alive)"
+            ),
+            SimulationError::InvalidDimensions { rows, columns } => {
+                write!(f, "A board with {rows}x{columns} fields is not allowed")
+            }
+            SimulationError::Overpopulated { animals, fields } => write!(
+                f,
+                "The amount of animals ({animals}) is bigger than the amount of fields ({fields})"
+            ),
+            SimulationError::OutOfBounds {
+                x,
+                y,
+                rows,
+                columns,
+            } => write!(
+                f,
+                "The position ({x}, {y}) is outside of the board with {rows}x{columns} fields"
+            ),
+            SimulationError::InvalidStatus { x, y, r#type } => {
+                write!(
+                    f,
+                    "The status for ({x}, {y}) does not belong to a {:?}",
+                    r#type
+                )
+            }
+            SimulationError::SharkWithoutLife => {
+                write!(f, "A shark needs a remaining life of at least one step")
+            }
+            SimulationError::InvalidConfig { key, message } => {
+                write!(f, "Invalid value for `{key}`: {message}")
+            }
+            SimulationError::ParseError {
+                line,
+                column,
+                message,
+            } => write!(f, "Parse error at line {line}, column {column}: {message}"),
+            SimulationError::Io { message, .. } => write!(f, "I/O error: {message}"),
+            SimulationError::UnsupportedSnapshotVersion { version } => {
+                write!(f, "Snapshot version {version} is not supported")
+            }
+            SimulationError::InvalidSnapshot { message } => {
+                write!(f, "Invalid snapshot: {message}")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for SimulationError {
+    fn from(error: io::Error) -> Self {
+        SimulationError::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
     }
 }
 
@@ -0,0 +1,174 @@
+//! Contains the optional log of the births and deaths of all animals of a board.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Board;
+//!
+//! let mut board = Board::new(20, 5, 10, 10).with_seed(42).with_lineage_log();
+//! board.generate_random_animals();
+//! for _ in 0..20 {
+//!     board.step().unwrap();
+//! }
+//!
+//! // The shark with the most children
+//! let lineage = board.lineage().unwrap();
+//! let parent = lineage
+//!     .iter()
+//!     .filter(|record| record.parent_id.is_none())
+//!     .max_by_key(|record| lineage.children(record.id).count());
+//! println!("{:?}", parent);
+//! ```
+use std::collections::BTreeMap;
+
+use crate::FieldType;
+
+/// The life of a single animal
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineageRecord {
+    /// The id of the animal
+    pub id: u64,
+    /// The id of the animal it was born from or `None` if it was placed on the board
+    pub parent_id: Option<u64>,
+    /// Whether the animal is a fish or a shark
+    pub r#type: FieldType,
+    /// The step in which the animal was born or placed on the board
+    pub born: u32,
+    /// The step in which the animal was eaten or starved, or after which it was removed from the
+    /// board with [`Board::place`](crate::Board::place) or [`Board::clear`](crate::Board::clear)
+    pub died: Option<u32>,
+}
+
+/// Records of every animal that lived on a board, ordered by id
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lineage {
+    records: BTreeMap<u64, LineageRecord>,
+}
+
+impl Lineage {
+    pub(crate) fn record_birth(
+        &mut self,
+        id: u64,
+        parent_id: Option<u64>,
+        r#type: FieldType,
+        step: u32,
+    ) {
+        self.records.insert(
+            id,
+            LineageRecord {
+                id,
+                parent_id,
+                r#type,
+                born: step,
+                died: None,
+            },
+        );
+    }
+
+    pub(crate) fn record_death(&mut self, id: u64, step: u32) {
+        if let Some(record) = self.records.get_mut(&id) {
+            record.died = Some(step);
+        }
+    }
+
+    /// Returns the record of an animal
+    ///
+    /// # Arguments
+    /// * `id` - The id of the animal
+    pub fn get(&self, id: u64) -> Option<&LineageRecord> {
+        self.records.get(&id)
+    }
+
+    /// Returns all records ordered by id
+    pub fn iter(&self) -> impl Iterator<Item = &LineageRecord> {
+        self.records.values()
+    }
+
+    /// Returns the records of the animals that were born from an animal
+    ///
+    /// # Arguments
+    /// * `id` - The id of the parent
+    pub fn children(&self, id: u64) -> impl Iterator<Item = &LineageRecord> {
+        self.records
+            .values()
+            .filter(move |record| record.parent_id == Some(id))
+    }
+
+    /// Returns the parent, grandparent and so on of an animal
+    ///
+    /// # Arguments
+    /// * `id` - The id of the animal
+    pub fn ancestors(&self, id: u64) -> impl Iterator<Item = &LineageRecord> {
+        let mut parent_id = self.get(id).and_then(|record| record.parent_id);
+        std::iter::from_fn(move || {
+            let parent = self.get(parent_id?)?;
+            parent_id = parent.parent_id;
+            Some(parent)
+        })
+    }
+
+    /// Returns the id of an animal that is its own ancestor or `None` if the log has no cycle
+    #[cfg(feature = "serde")]
+    pub(crate) fn find_cycle(&self) -> Option<u64> {
+        let mut acyclic = std::collections::HashSet::new();
+        for id in self.records.keys() {
+            let mut path = std::collections::HashSet::new();
+            let mut current = Some(*id);
+            while let Some(id) = current.filter(|id| !acyclic.contains(id)) {
+                if !path.insert(id) {
+                    return Some(id);
+                }
+                current = self.get(id).and_then(|record| record.parent_id);
+            }
+            acyclic.extend(path);
+        }
+        None
+    }
+
+    /// Returns the amount of animals in the log
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no animal was logged yet
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_family_tree() {
+        let mut lineage = Lineage::default();
+        lineage.record_birth(1, None, FieldType::Shark, 0);
+        lineage.record_birth(2, Some(1), FieldType::Shark, 3);
+        lineage.record_birth(3, Some(2), FieldType::Shark, 6);
+        lineage.record_birth(4, Some(1), FieldType::Shark, 7);
+        lineage.record_death(1, 8);
+
+        assert_eq!(lineage.len(), 4);
+        assert_eq!(lineage.get(1).unwrap().died, Some(8));
+        let children: Vec<u64> = lineage.children(1).map(|record| record.id).collect();
+        assert_eq!(children, vec![2, 4]);
+        let ancestors: Vec<u64> = lineage.ancestors(3).map(|record| record.id).collect();
+        assert_eq!(ancestors, vec![2, 1]);
+        assert_eq!(lineage.ancestors(1).count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_find_cycle() {
+        let mut lineage = Lineage::default();
+        lineage.record_birth(1, None, FieldType::Fish, 0);
+        lineage.record_birth(2, Some(1), FieldType::Fish, 2);
+        lineage.record_birth(3, Some(7), FieldType::Fish, 2);
+        assert_eq!(lineage.find_cycle(), None);
+
+        lineage.record_birth(1, Some(2), FieldType::Fish, 0);
+        assert_eq!(lineage.find_cycle(), Some(1));
+    }
+}
@@ -0,0 +1,653 @@
+//! Command line interface to run simulations without writing Rust (requires the `cli` feature).
+//!
+//! ```bash
+//! # Statistics of 500 steps on a 60x60 board as CSV
+//! planetensimulation run --rows 60 --columns 60 --fishes 800 --sharks 200 --steps 500
+//!
+//! # Every combination of breed times, three times each
+//! planetensimulation sweep --fish-breed-times 2,3,4 --shark-breed-times 6,8 --repeats 3
+//!
+//! # Averages over ten runs per initial population
+//! planetensimulation sweep --fish-densities 0.05,0.1,0.2 --repeats 10 --summary
+//!
+//! # Mean, 90% band and extinction probability of 500 replicates
+//! planetensimulation ensemble --replicates 500 --quantiles 0.05,0.5,0.95
+//!
+//! # Watch a hand-crafted scenario
+//! planetensimulation render --layout examples/scenarios/surrounded_shark.txt
+//!
+//! # Run a scenario file, the command line overrides its settings
+//! planetensimulation run --scenario examples/scenarios/many_sharks.toml --steps 50
+//!
+//! # Save the board and continue the simulation later
+//! planetensimulation run --snapshot board.json --steps 100
+//! planetensimulation replay board.json --steps 100 --format matlab --output simulation.m
+//! ```
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use clap::{Args, Parser, Subcommand};
+use planetensimulation::{
+    Board, BoardBuilder, CsvWriter, Ensemble, EnsembleStatistics, ExtinctionPolicy, MatlabScript,
+    Neighbourhood, Output, OutputFormat, Scenario, Statistics, Sweep, SweepTable, Topology,
+    UpdateOrder,
+};
+
+type CliResult<T = ()> = Result<T, Box<dyn Error>>;
+
+/// Simulates sharks and fishes on a planet
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs a simulation and writes the statistics of every step
+    Run {
+        #[command(flatten)]
+        board: BoardArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Saves the board after the last step as a snapshot (JSON if the file ends with
+        /// `.json`, binary otherwise)
+        #[arg(long)]
+        snapshot: Option<PathBuf>,
+    },
+    /// Runs simulations for every combination of breed times and initial populations in
+    /// parallel and writes one row per run
+    Sweep {
+        #[command(flatten)]
+        board: BoardArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// The breed times of the fishes (defaults to the breed time of the board)
+        #[arg(long, value_delimiter = ',')]
+        fish_breed_times: Vec<u32>,
+        /// The breed times of the sharks (defaults to the breed time of the board)
+        #[arg(long, value_delimiter = ',')]
+        shark_breed_times: Vec<u32>,
+        /// The initial amounts of fishes (defaults to the amount of the board)
+        #[arg(long, value_delimiter = ',')]
+        fish_amounts: Vec<u32>,
+        /// The initial amounts of sharks (defaults to the amount of the board)
+        #[arg(long, value_delimiter = ',')]
+        shark_amounts: Vec<u32>,
+        /// The initial shares of the fields that are occupied by fishes from 0 to 1
+        #[arg(long, value_delimiter = ',', conflicts_with = "fish_amounts")]
+        fish_densities: Vec<f64>,
+        /// The initial shares of the fields that are occupied by sharks from 0 to 1
+        #[arg(long, value_delimiter = ',', conflicts_with = "shark_amounts")]
+        shark_densities: Vec<f64>,
+        /// How often every combination is simulated with a different seed
+        #[arg(long, default_value_t = 1)]
+        repeats: u32,
+        /// The amount of threads (all CPU cores if not set)
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Writes one row per combination with the results averaged over the repeats
+        #[arg(long)]
+        summary: bool,
+    },
+    /// Runs seeded replicates of one board in parallel and writes the distribution of the
+    /// populations in every step
+    Ensemble {
+        #[command(flatten)]
+        board: BoardArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// The amount of replicates
+        #[arg(long, default_value_t = 100)]
+        replicates: u32,
+        /// The quantiles of the bands
+        #[arg(long, value_delimiter = ',', default_values_t = [0.05, 0.25, 0.5, 0.75, 0.95])]
+        quantiles: Vec<f64>,
+        /// The amount of threads (all CPU cores if not set)
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// Prints the board after every step
+    Render {
+        #[command(flatten)]
+        board: BoardArgs,
+        /// The time between two steps in milliseconds
+        #[arg(long, default_value_t = 200)]
+        delay: u64,
+    },
+    /// Continues a simulation from a snapshot and writes the statistics of every step
+    Replay {
+        /// The snapshot that was saved with `run --snapshot`
+        snapshot: PathBuf,
+        /// The maximum amount of steps that are simulated
+        #[arg(long, default_value_t = DEFAULT_STEPS)]
+        steps: u32,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+/// Parameters that describe the board
+#[derive(Args)]
+struct BoardArgs {
+    /// Reads the board from a file in the plain-text format instead of placing the animals
+    /// randomly
+    #[arg(long, conflicts_with_all = ["rows", "columns", "fishes", "sharks"])]
+    layout: Option<PathBuf>,
+    /// Reads the board, the steps and the output from a TOML scenario file, the other
+    /// parameters override the ones of the scenario
+    #[arg(long, conflicts_with_all = ["layout", "rows", "columns", "fishes", "sharks"])]
+    scenario: Option<PathBuf>,
+    /// The amount of rows
+    #[arg(long, default_value_t = 40)]
+    rows: u32,
+    /// The amount of columns
+    #[arg(long, default_value_t = 40)]
+    columns: u32,
+    /// The amount of fishes that are placed randomly
+    #[arg(long, default_value_t = 200)]
+    fishes: u32,
+    /// The amount of sharks that are placed randomly
+    #[arg(long, default_value_t = 100)]
+    sharks: u32,
+    /// The seed of the random number generator (random if not set)
+    #[arg(long)]
+    seed: Option<u64>,
+    /// The maximum amount of steps that are simulated [default: 100 or the steps of the
+    /// scenario]
+    #[arg(long)]
+    steps: Option<u32>,
+    /// The amount of steps after which a fish breeds
+    #[arg(long)]
+    fish_breed_time: Option<u32>,
+    /// The amount of steps after which a shark breeds
+    #[arg(long)]
+    shark_breed_time: Option<u32>,
+    /// The amount of steps a shark survives without eating a fish
+    #[arg(long)]
+    shark_starvation_time: Option<u32>,
+    /// torus, box, horizontal_cylinder or vertical_cylinder
+    #[arg(long)]
+    topology: Option<Topology>,
+    /// von_neumann, moore or hexagonal
+    #[arg(long)]
+    neighbourhood: Option<Neighbourhood>,
+    /// sequential, shuffled or synchronous
+    #[arg(long)]
+    update_order: Option<UpdateOrder>,
+    /// continue, stop_on_fish_extinct, stop_on_sharks_extinct or stop_on_any_extinct
+    /// [default: stop_on_any_extinct unless the scenario or layout sets it]
+    #[arg(long)]
+    extinction_policy: Option<ExtinctionPolicy>,
+}
+
+const DEFAULT_STEPS: u32 = 100;
+
+impl BoardArgs {
+    /// Reads the scenario file if one was given
+    fn scenario(&self) -> CliResult<Option<Scenario>> {
+        match &self.scenario {
+            Some(path) => Ok(Some(Scenario::from_reader(File::open(path)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the maximum amount of steps from the command line or the scenario
+    fn steps(&self, scenario: Option<&Scenario>) -> u32 {
+        self.steps
+            .or_else(|| scenario.and_then(|scenario| scenario.steps))
+            .unwrap_or(DEFAULT_STEPS)
+    }
+
+    /// Describes the board of the scenario, the layout file or with randomly placed animals
+    /// and applies the parameters that were given on the command line
+    ///
+    /// # Arguments
+    /// * `scenario` - The scenario that was read with [`BoardArgs::scenario`]
+    fn builder(&self, scenario: Option<&Scenario>) -> CliResult<BoardBuilder> {
+        let mut builder = match (scenario, &self.layout) {
+            (Some(scenario), _) => scenario.builder().clone(),
+            (None, Some(layout)) => fs::read_to_string(layout)?.parse()?,
+            (None, None) => Board::builder()
+                .dimensions(self.rows, self.columns)
+                .fishes(self.fishes)
+                .sharks(self.sharks)
+                .extinction_policy(ExtinctionPolicy::StopOnAnyExtinct),
+        };
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(fish_breed_time) = self.fish_breed_time {
+            builder = builder.fish_breed_time(fish_breed_time);
+        }
+        if let Some(shark_breed_time) = self.shark_breed_time {
+            builder = builder.shark_breed_time(shark_breed_time);
+        }
+        if let Some(shark_starvation_time) = self.shark_starvation_time {
+            builder = builder.shark_starvation_time(shark_starvation_time);
+        }
+        if let Some(topology) = self.topology {
+            builder = builder.topology(topology);
+        }
+        if let Some(neighbourhood) = self.neighbourhood {
+            builder = builder.neighbourhood(neighbourhood);
+        }
+        if let Some(update_order) = self.update_order {
+            builder = builder.update_order(update_order);
+        }
+        if let Some(policy) = self.extinction_policy {
+            builder = builder.extinction_policy(policy);
+        }
+        Ok(builder)
+    }
+}
+
+/// Where and in which format the results are written
+#[derive(Args)]
+struct OutputArgs {
+    /// csv, json or matlab [default: csv or the format of the scenario]
+    #[arg(long)]
+    format: Option<OutputFormat>,
+    /// The file the results are written to (standard output if not set)
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    /// Completes the output of the scenario with the parameters of the command line
+    ///
+    /// If only the format is overridden, the file of the scenario gets the extension of the new
+    /// format, e.g. `real.csv` becomes `real.json`.
+    fn resolve(self, scenario: Option<&Scenario>) -> Output {
+        let defaults = scenario
+            .and_then(|scenario| scenario.output.clone())
+            .unwrap_or_default();
+        let format = self.format.unwrap_or(defaults.format);
+        let path = match self.output {
+            Some(path) => Some(path),
+            None if format != defaults.format => defaults
+                .path
+                .map(|path| path.with_extension(format.extension())),
+            None => defaults.path,
+        };
+        Output { format, path }
+    }
+}
+
+fn writer(output: &Output) -> CliResult<Box<dyn Write>> {
+    Ok(match &output.path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    })
+}
+
+fn main() {
+    if let Err(error) = Cli::parse().command.execute() {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
+}
+
+impl Command {
+    fn execute(self) -> CliResult {
+        match self {
+            Command::Run {
+                board: args,
+                output,
+                snapshot,
+            } => {
+                let scenario = args.scenario()?;
+                let scenario = scenario.as_ref();
+                let mut board = args.builder(scenario)?.build()?;
+                simulate(&mut board, args.steps(scenario));
+                let name = scenario
+                    .and_then(|scenario| scenario.name.as_deref())
+                    .unwrap_or("run");
+                write_statistics(board.statistics(), name, &output.resolve(scenario))?;
+                if let Some(path) = snapshot {
+                    save_snapshot(&board, &path)?;
+                }
+                Ok(())
+            }
+            Command::Sweep {
+                board: args,
+                output,
+                fish_breed_times,
+                shark_breed_times,
+                fish_amounts,
+                shark_amounts,
+                fish_densities,
+                shark_densities,
+                repeats,
+                threads,
+                summary,
+            } => {
+                let scenario = args.scenario()?;
+                let scenario = scenario.as_ref();
+                let mut sweep = Sweep::new(args.builder(scenario)?)
+                    .fish_breed_times(fish_breed_times)
+                    .shark_breed_times(shark_breed_times)
+                    .fishes(fish_amounts)
+                    .sharks(shark_amounts)
+                    .repeats(repeats)
+                    .steps(args.steps(scenario));
+                if !fish_densities.is_empty() {
+                    sweep = sweep.fish_densities(fish_densities);
+                }
+                if !shark_densities.is_empty() {
+                    sweep = sweep.shark_densities(shark_densities);
+                }
+                if let Some(seed) = args.seed.or_else(|| scenario.and_then(Scenario::seed)) {
+                    sweep = sweep.seed(seed);
+                }
+                if let Some(threads) = threads {
+                    sweep = sweep.threads(threads);
+                }
+                write_table(&sweep.run()?, summary, &output.resolve(scenario))
+            }
+            Command::Ensemble {
+                board: args,
+                output,
+                replicates,
+                quantiles,
+                threads,
+            } => {
+                let scenario = args.scenario()?;
+                let scenario = scenario.as_ref();
+                let mut ensemble = Ensemble::new(args.builder(scenario)?)
+                    .replicates(replicates)
+                    .quantiles(quantiles)
+                    .steps(args.steps(scenario));
+                if let Some(seed) = args.seed.or_else(|| scenario.and_then(Scenario::seed)) {
+                    ensemble = ensemble.seed(seed);
+                }
+                if let Some(threads) = threads {
+                    ensemble = ensemble.threads(threads);
+                }
+                write_ensemble(&ensemble.run()?, &output.resolve(scenario))
+            }
+            Command::Render { board: args, delay } => {
+                let scenario = args.scenario()?;
+                let scenario = scenario.as_ref();
+                let mut board = args.builder(scenario)?.build()?;
+                println!("{board}");
+                for _ in 0..args.steps(scenario) {
+                    if board.step().is_err() {
+                        break;
+                    }
+                    thread::sleep(Duration::from_millis(delay));
+                    println!("{board}");
+                }
+                Ok(())
+            }
+            Command::Replay {
+                snapshot,
+                steps,
+                output,
+            } => {
+                let mut board = load_snapshot(&snapshot)?;
+                simulate(&mut board, steps);
+                write_statistics(
+                    board.statistics(),
+                    &snapshot.display().to_string(),
+                    &output.resolve(None),
+                )
+            }
+        }
+    }
+}
+
+/// Simulates until the step limit is reached or the extinction policy stops the board
+fn simulate(board: &mut Board, steps: u32) {
+    for _ in 0..steps {
+        if board.step().is_err() {
+            break;
+        }
+    }
+}
+
+fn write_statistics(statistics: &Statistics, name: &str, output: &Output) -> CliResult {
+    let mut writer = writer(output)?;
+    match output.format {
+        OutputFormat::Csv => {
+            let mut csv = CsvWriter::new(writer)?;
+            csv.write_statistics(statistics)?;
+            csv.into_inner()?;
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, statistics.records())?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        OutputFormat::Matlab => {
+            MatlabScript::new("Planetensimulation")
+                .run(name, statistics)
+                .write(&mut writer)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn write_table(table: &SweepTable, summary: bool, output: &Output) -> CliResult {
+    let mut writer = writer(output)?;
+    match output.format {
+        OutputFormat::Csv if summary => table.write_summary_csv(writer)?,
+        OutputFormat::Csv => table.write_csv(writer)?,
+        OutputFormat::Json => {
+            if summary {
+                serde_json::to_writer_pretty(&mut writer, &table.summaries())?;
+            } else {
+                serde_json::to_writer_pretty(&mut writer, table.runs())?;
+            }
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        OutputFormat::Matlab => return Err("a sweep can only be written as CSV or JSON".into()),
+    }
+    Ok(())
+}
+
+fn write_ensemble(statistics: &EnsembleStatistics, output: &Output) -> CliResult {
+    let mut writer = writer(output)?;
+    match output.format {
+        OutputFormat::Csv => statistics.write_csv(writer)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, statistics)?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        OutputFormat::Matlab => return Err("an ensemble can only be written as CSV or JSON".into()),
+    }
+    Ok(())
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+}
+
+fn save_snapshot(board: &Board, path: &Path) -> CliResult {
+    if is_json(path) {
+        fs::write(path, board.to_json()?)?;
+    } else {
+        fs::write(path, board.to_bytes()?)?;
+    }
+    Ok(())
+}
+
+fn load_snapshot(path: &Path) -> CliResult<Board> {
+    if is_json(path) {
+        Ok(Board::from_json(&fs::read_to_string(path)?)?)
+    } else {
+        Ok(Board::from_bytes(&fs::read(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_arguments_are_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_enums() {
+        let cli = Cli::try_parse_from([
+            "planetensimulation",
+            "run",
+            "--topology",
+            "box",
+            "--update-order",
+            "synchronous",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        let Command::Run { board, output, .. } = cli.command else {
+            panic!("expected the run command");
+        };
+        assert_eq!(board.topology, Some(Topology::Box));
+        assert_eq!(board.update_order, Some(UpdateOrder::Synchronous));
+        assert_eq!(output.format, Some(OutputFormat::Json));
+
+        assert!(
+            Cli::try_parse_from(["planetensimulation", "run", "--topology", "sphere"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_sweep_creates_every_combination() {
+        let cli = Cli::try_parse_from([
+            "planetensimulation",
+            "sweep",
+            "--rows",
+            "10",
+            "--columns",
+            "10",
+            "--fishes",
+            "20",
+            "--sharks",
+            "5",
+            "--fish-breed-times",
+            "2,3",
+            "--shark-amounts",
+            "5,10",
+            "--seed",
+            "1",
+        ])
+        .unwrap();
+        let Command::Sweep {
+            board,
+            fish_breed_times,
+            shark_amounts,
+            ..
+        } = cli.command
+        else {
+            panic!("expected the sweep command");
+        };
+        assert_eq!(fish_breed_times, vec![2, 3]);
+
+        let table = Sweep::new(board.builder(None).unwrap())
+            .fish_breed_times(fish_breed_times)
+            .sharks(shark_amounts)
+            .steps(5)
+            .run()
+            .unwrap();
+        assert_eq!(table.runs().len(), 4);
+        assert_eq!(table.runs()[1].parameters.fish_breed_time, 2);
+        assert_eq!(table.runs()[1].parameters.sharks, 10);
+        assert_eq!(table.runs()[1].parameters.fishes, 20);
+
+        assert!(Cli::try_parse_from([
+            "planetensimulation",
+            "sweep",
+            "--fish-amounts",
+            "10",
+            "--fish-densities",
+            "0.1",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_ensemble_quantiles() {
+        let cli = Cli::try_parse_from(["planetensimulation", "ensemble"]).unwrap();
+        let Command::Ensemble { quantiles, .. } = cli.command else {
+            panic!("expected the ensemble command");
+        };
+        assert_eq!(quantiles, vec![0.05, 0.25, 0.5, 0.75, 0.95]);
+
+        let cli = Cli::try_parse_from(["planetensimulation", "ensemble", "--quantiles", "0.1,0.9"])
+            .unwrap();
+        let Command::Ensemble { quantiles, .. } = cli.command else {
+            panic!("expected the ensemble command");
+        };
+        assert_eq!(quantiles, vec![0.1, 0.9]);
+    }
+
+    #[test]
+    fn test_command_line_overrides_scenario() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/scenarios/real.toml");
+        let cli = Cli::try_parse_from([
+            "planetensimulation",
+            "run",
+            "--scenario",
+            path,
+            "--shark-breed-time",
+            "5",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        let Command::Run { board, output, .. } = cli.command else {
+            panic!("expected the run command");
+        };
+        let scenario = board.scenario().unwrap().unwrap();
+        assert_eq!(board.steps(Some(&scenario)), 1000);
+
+        let created = board.builder(Some(&scenario)).unwrap().build().unwrap();
+        assert_eq!(created.count_animals(), (200, 100));
+        assert_eq!(created.config().fish_breed_time, 3);
+        assert_eq!(created.config().shark_breed_time, 5);
+
+        let output = output.resolve(Some(&scenario));
+        assert_eq!(output.format, OutputFormat::Json);
+        assert_eq!(output.path, Some("real.json".into()));
+
+        let output = OutputArgs {
+            format: Some(OutputFormat::Matlab),
+            output: Some("statistics.txt".into()),
+        };
+        let output = output.resolve(Some(&scenario));
+        assert_eq!(output.path, Some("statistics.txt".into()));
+        let output = OutputArgs {
+            format: Some(OutputFormat::Csv),
+            output: None,
+        };
+        assert_eq!(
+            output.resolve(Some(&scenario)).path,
+            Some("real.csv".into())
+        );
+
+        assert!(Cli::try_parse_from([
+            "planetensimulation",
+            "run",
+            "--scenario",
+            path,
+            "--rows",
+            "5"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Contains an exporter that writes the population history of one or more boards as a
+//! MATLAB/Octave script.
+//!
+//! # Examples
+//! ```no_run
+//! use planetensimulation::{Board, MatlabScript};
+//!
+//! let mut board = Board::new(200, 100, 40, 40).with_seed(42);
+//! board.generate_random_animals();
+//! for _ in 0..200 {
+//!     board.step().unwrap();
+//! }
+//!
+//! let file = std::fs::File::create("simulation.m").unwrap();
+//! MatlabScript::new("Planetensimulation")
+//!     .run("seed 42", board.statistics())
+//!     .write(file)
+//!     .unwrap();
+//! ```
+use std::io::{self, Write};
+
+use crate::Statistics;
+
+/// A MATLAB/Octave script that plots the population over time and the phase space of fishes
+/// and sharks
+#[derive(Clone, Debug)]
+pub struct MatlabScript<'a> {
+    title: String,
+    runs: Vec<(String, &'a Statistics)>,
+}
+
+impl<'a> MatlabScript<'a> {
+    /// Creates a script without any runs
+    ///
+    /// # Arguments
+    /// * `title` - The title of the figure
+    pub fn new(title: &str) -> Self {
+        MatlabScript {
+            title: title.into(),
+            runs: vec![],
+        }
+    }
+
+    /// Adds the history of a board to the plots
+    ///
+    /// # Arguments
+    /// * `name` - The name of the run that is shown in the legend
+    /// * `statistics` - The recorded history of the board
+    pub fn run(mut self, name: &str, statistics: &'a Statistics) -> Self {
+        self.runs.push((name.into(), statistics));
+        self
+    }
+
+    /// Writes the script
+    ///
+    /// # Arguments
+    /// * `writer` - The destination of the script, usually a `.m` file
+    ///
+    /// # Errors
+    /// If the script could not be written
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "% {}", self.title.replace('\n', " "))?;
+        for (index, (_, statistics)) in self.runs.iter().enumerate() {
+            let run = index + 1;
+            let steps = statistics.iter().map(|record| record.step);
+            writeln!(writer, "steps_{run} = [{}];", Self::vector(steps))?;
+            writeln!(
+                writer,
+                "fishes_{run} = [{}];",
+                Self::vector(statistics.fishes())
+            )?;
+            writeln!(
+                writer,
+                "sharks_{run} = [{}];",
+                Self::vector(statistics.sharks())
+            )?;
+        }
+
+        writeln!(writer, "figure('Name', '{}');", Self::escape(&self.title))?;
+
+        writeln!(writer, "subplot(1, 2, 1);")?;
+        writeln!(writer, "hold on")?;
+        for (index, (name, _)) in self.runs.iter().enumerate() {
+            let (run, name) = (index + 1, Self::escape(name));
+            writeln!(
+                writer,
+                "plot(steps_{run}, fishes_{run}, '-', 'DisplayName', 'Fishes ({name})');"
+            )?;
+            writeln!(
+                writer,
+                "plot(steps_{run}, sharks_{run}, '--', 'DisplayName', 'Sharks ({name})');"
+            )?;
+        }
+        writeln!(writer, "hold off")?;
+        writeln!(writer, "title('Population over time');")?;
+        writeln!(writer, "xlabel('Step');")?;
+        writeln!(writer, "ylabel('Animals');")?;
+        writeln!(writer, "legend('show');")?;
+
+        writeln!(writer, "subplot(1, 2, 2);")?;
+        writeln!(writer, "hold on")?;
+        for (index, (name, _)) in self.runs.iter().enumerate() {
+            let (run, name) = (index + 1, Self::escape(name));
+            writeln!(
+                writer,
+                "plot(fishes_{run}, sharks_{run}, 'DisplayName', '{name}');"
+            )?;
+        }
+        writeln!(writer, "hold off")?;
+        writeln!(writer, "title('Phase space');")?;
+        writeln!(writer, "xlabel('Fishes');")?;
+        writeln!(writer, "ylabel('Sharks');")?;
+        writeln!(writer, "legend('show');")?;
+
+        writer.flush()
+    }
+
+    fn vector(values: impl Iterator<Item = u32>) -> String {
+        values
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Escapes a string for a single quoted MATLAB string literal
+    fn escape(text: &str) -> String {
+        text.replace('\'', "''").replace('\n', " ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Board, ExtinctionPolicy};
+
+    fn simulate(seed: u64, steps: u32) -> Board {
+        let mut board = Board::new(10, 5, 6, 6)
+            .with_seed(seed)
+            .with_extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+        board.generate_random_animals();
+        for _ in 0..steps {
+            if board.step().is_err() {
+                break;
+            }
+        }
+        board
+    }
+
+    fn write(script: &MatlabScript) -> String {
+        let mut output = Vec::new();
+        script.write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_vectors_have_the_length_of_the_history() {
+        let board = simulate(3, 4);
+        let script = write(&MatlabScript::new("Test").run("run", board.statistics()));
+
+        let length = board.statistics().len();
+        for name in ["steps_1", "fishes_1", "sharks_1"] {
+            let line = script
+                .lines()
+                .find(|line| line.starts_with(&format!("{name} = [")))
+                .unwrap();
+            assert_eq!(line.matches(',').count() + 1, length);
+        }
+        assert!(script.contains("plot(steps_1, fishes_1"));
+        assert!(script.contains("plot(fishes_1, sharks_1"));
+    }
+
+    #[test]
+    fn test_multiple_runs() {
+        let (first, second) = (simulate(1, 3), simulate(2, 5));
+        let script = write(
+            &MatlabScript::new("Shark's world")
+                .run("first", first.statistics())
+                .run("second", second.statistics()),
+        );
+
+        assert!(script.contains("figure('Name', 'Shark''s world');"));
+        assert!(script.contains("steps_2 = ["));
+        assert!(script.contains("'Fishes (second)'"));
+        assert!(script.contains("plot(fishes_2, sharks_2, 'DisplayName', 'second');"));
+    }
+}
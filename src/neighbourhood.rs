@@ -0,0 +1,147 @@
+//! Contains the neighbourhoods that define which fields an animal can move to or hunt on.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, Neighbourhood};
+//!
+//! // Animals can also move diagonally
+//! let mut board = Board::new(10, 5, 25, 25).with_neighbourhood(Neighbourhood::Moore);
+//! board.generate_random_animals();
+//! ```
+use std::str::FromStr;
+
+use crate::{Result, SimulationError, Topology};
+
+type Position = (u32, u32);
+
+const VON_NEUMANN: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const MOORE: [(i32, i32); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+const HEXAGONAL_EVEN_ROW: [(i32, i32); 6] = [(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)];
+const HEXAGONAL_ODD_ROW: [(i32, i32); 6] = [(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// Describes which fields around an animal are its neighbours
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighbourhood {
+    /// The four fields above, below, left and right
+    #[default]
+    VonNeumann,
+    /// The eight fields around including the diagonals
+    Moore,
+    /// The six fields of a hexagonal grid where every odd row is shifted half a field to the
+    /// right
+    ///
+    /// On a board that wraps vertically the amount of rows should be even, otherwise the shift
+    /// of the first and the last row does not match.
+    Hexagonal,
+}
+
+impl Neighbourhood {
+    /// Returns the offsets of the neighbours in columns and rows
+    ///
+    /// # Arguments
+    /// * `y` - The row of the field (only the hexagonal neighbourhood depends on it)
+    pub fn offsets(&self, y: u32) -> &'static [(i32, i32)] {
+        match self {
+            Neighbourhood::VonNeumann => &VON_NEUMANN,
+            Neighbourhood::Moore => &MOORE,
+            Neighbourhood::Hexagonal if y % 2 == 1 => &HEXAGONAL_ODD_ROW,
+            Neighbourhood::Hexagonal => &HEXAGONAL_EVEN_ROW,
+        }
+    }
+
+    /// Returns the positions of the neighbours of a field
+    ///
+    /// # Arguments
+    /// * `position` - The position of the field
+    /// * `topology` - Describes which edges of the board wrap around
+    /// * `rows` - The amount of rows of the board
+    /// * `columns` - The amount of columns of the board
+    ///
+    /// # Returns
+    /// The neighbours that are not behind a wall
+    pub fn positions(
+        &self,
+        position: Position,
+        topology: Topology,
+        rows: u32,
+        columns: u32,
+    ) -> impl Iterator<Item = Position> {
+        self.offsets(position.1)
+            .iter()
+            .filter_map(move |offset| topology.offset(position, *offset, rows, columns))
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = SimulationError;
+
+    /// Parses the snake case name of a neighbourhood, e.g. `von_neumann`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "von_neumann" => Ok(Neighbourhood::VonNeumann),
+            "moore" => Ok(Neighbourhood::Moore),
+            "hexagonal" => Ok(Neighbourhood::Hexagonal),
+            _ => Err(SimulationError::InvalidConfig {
+                key: "neighbourhood".into(),
+                message: format!(
+                    "unknown neighbourhood `{name}`, expected one of von_neumann, moore, hexagonal"
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn neighbours(neighbourhood: Neighbourhood, position: Position) -> Vec<Position> {
+        neighbourhood
+            .positions(position, Topology::Torus, 6, 6)
+            .collect()
+    }
+
+    #[test]
+    fn test_amount_of_neighbours() {
+        assert_eq!(neighbours(Neighbourhood::VonNeumann, (2, 2)).len(), 4);
+        assert_eq!(neighbours(Neighbourhood::Moore, (2, 2)).len(), 8);
+        assert_eq!(neighbours(Neighbourhood::Hexagonal, (2, 2)).len(), 6);
+    }
+
+    #[test]
+    fn test_hexagonal_rows_are_shifted() {
+        let even = neighbours(Neighbourhood::Hexagonal, (2, 2));
+        assert_eq!(even, vec![(1, 1), (2, 1), (1, 2), (3, 2), (1, 3), (2, 3)]);
+        let odd = neighbours(Neighbourhood::Hexagonal, (2, 3));
+        assert_eq!(odd, vec![(2, 2), (3, 2), (1, 3), (3, 3), (2, 4), (3, 4)]);
+    }
+
+    #[test]
+    fn test_hexagonal_is_symmetric() {
+        for y in 0..6 {
+            for x in 0..6 {
+                for neighbour in neighbours(Neighbourhood::Hexagonal, (x, y)) {
+                    assert!(neighbours(Neighbourhood::Hexagonal, neighbour).contains(&(x, y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_moore_in_box_corner() {
+        let corner: Vec<Position> = Neighbourhood::Moore
+            .positions((0, 0), Topology::Box, 6, 6)
+            .collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+    }
+}
@@ -0,0 +1,509 @@
+//! Contains the parser for the plain-text board format.
+//!
+//! A board is written as a grid of the glyphs that are used by the `Display` implementation of
+//! a field: `F` for a fish, `S` for a shark and `_` for plankton. The glyphs of a row may be
+//! separated by commas or whitespace, and the colours of the `Display` implementation are
+//! ignored, so the output of `board.to_string()` can be parsed again. The grid can be preceded
+//! by header lines of the form `key = value` that set the parameters of the simulation. Every
+//! key may be set once. Lines starting with `#` and empty lines are ignored.
+//!
+//! Supported header keys are `fish_breed_time`, `shark_breed_time`, `shark_starvation_time`,
+//! `initial_shark_life` (e.g. `1..8`), `topology`, `neighbourhood`, `update_order`,
+//! `extinction_policy` and `seed`.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Board;
+//!
+//! // One shark surrounded by fishes
+//! let board: Board = "
+//!     shark_starvation_time = 2
+//!     seed = 42
+//!     _____
+//!     __F__
+//!     _FSF_
+//!     __F__
+//!     _____
+//! "
+//! .parse()
+//! .unwrap();
+//! assert_eq!(board.count_animals(), (4, 1));
+//! ```
+use std::{io::Read, ops::Range, str::FromStr};
+
+use crate::{
+    field::FieldType, Board, BoardBuilder, ExtinctionPolicy, Neighbourhood, Result,
+    SimulationConfig, SimulationError, Topology, UpdateOrder,
+};
+
+type Position = (u32, u32);
+
+impl FromStr for Board {
+    type Err = SimulationError;
+
+    /// Parses a board from the plain-text format
+    ///
+    /// # Errors
+    /// * [`SimulationError::ParseError`] with the line and column of the first invalid character
+    ///   or header value
+    fn from_str(text: &str) -> Result<Self> {
+        Parser::default().parse(text)?.finish()
+    }
+}
+
+impl FromStr for BoardBuilder {
+    type Err = SimulationError;
+
+    /// Parses the plain-text format into a builder that recreates the board
+    ///
+    /// Unlike a parsed [`Board`] the builder can still be changed, e.g. to simulate the same
+    /// layout with different seeds.
+    ///
+    /// # Errors
+    /// * [`SimulationError::ParseError`] with the line and column of the first invalid character
+    ///   or header value
+    fn from_str(text: &str) -> Result<Self> {
+        let mut parser = Parser::default().parse(text)?;
+        let layout = parser.layout()?;
+        parser.validate()?;
+
+        let mut builder = BoardBuilder::default()
+            .layout(&layout.grid())
+            .config(parser.config)
+            .topology(parser.topology)
+            .neighbourhood(parser.neighbourhood)
+            .update_order(parser.update_order)
+            .extinction_policy(parser.extinction_policy);
+        if let Some(seed) = parser.seed {
+            builder = builder.seed(seed);
+        }
+        Ok(builder)
+    }
+}
+
+/// The size of a board and the positions of its animals
+pub(crate) struct Layout {
+    pub(crate) rows: u32,
+    pub(crate) columns: u32,
+    pub(crate) animals: Vec<(Position, FieldType)>,
+}
+
+impl Layout {
+    /// Draws the layout as a grid of glyphs
+    fn grid(&self) -> String {
+        let mut rows = vec![vec!['_'; self.columns as usize]; self.rows as usize];
+        for ((x, y), r#type) in &self.animals {
+            rows[*y as usize][*x as usize] = match r#type {
+                FieldType::Fish => 'F',
+                FieldType::Shark => 'S',
+                FieldType::Plankton => '_',
+            };
+        }
+        rows.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Parses a grid of glyphs without header lines
+///
+/// # Errors
+/// * [`SimulationError::ParseError`] with the line and column of the first invalid character
+pub(crate) fn parse_layout(text: &str) -> Result<Layout> {
+    let mut parser = Parser {
+        grid_only: true,
+        ..Parser::default()
+    }
+    .parse(text)?;
+    parser.layout()
+}
+
+impl Board {
+    /// Reads a board in the plain-text format
+    ///
+    /// # Arguments
+    /// * `reader` - The source of the text, e.g. a scenario file
+    ///
+    /// # Errors
+    /// * [`SimulationError::Io`] if reading fails
+    /// * [`SimulationError::ParseError`] if the text is not a valid board
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        text.parse()
+    }
+}
+
+/// Collects the header values and the grid while the text is read line by line
+#[derive(Default)]
+struct Parser {
+    config: SimulationConfig,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    update_order: UpdateOrder,
+    extinction_policy: ExtinctionPolicy,
+    seed: Option<u64>,
+    /// The line and column of the value of every header key that was set
+    keys: Vec<(String, usize, usize)>,
+    animals: Vec<(Position, FieldType)>,
+    rows: u32,
+    columns: Option<u32>,
+    last_line: usize,
+    /// Header lines are rejected
+    grid_only: bool,
+}
+
+impl Parser {
+    fn parse(mut self, text: &str) -> Result<Self> {
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let content = line.trim();
+            self.last_line = number;
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let indent = column_of(line, line.len() - line.trim_start().len());
+            if let Some(separator) = line.find('=') {
+                if self.grid_only {
+                    return Err(error(number, indent, "a layout contains no header lines"));
+                }
+                if self.rows > 0 {
+                    return Err(error(number, indent, "header line after the grid"));
+                }
+                self.parse_header(line, separator, number, indent)?;
+            } else {
+                self.parse_row(line, number)?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn parse_header(
+        &mut self,
+        line: &str,
+        separator: usize,
+        number: usize,
+        indent: usize,
+    ) -> Result {
+        let key = line[..separator].trim();
+        let after = &line[separator + 1..];
+        let value = after.trim();
+        let column = column_of(line, line.len() - after.trim_start().len());
+        let invalid = |message: String| error(number, column, &message);
+
+        match key {
+            "fish_breed_time" => {
+                self.config.fish_breed_time = parse_number(value).map_err(invalid)?
+            }
+            "shark_breed_time" => {
+                self.config.shark_breed_time = parse_number(value).map_err(invalid)?
+            }
+            "shark_starvation_time" => {
+                self.config.shark_starvation_time = parse_number(value).map_err(invalid)?
+            }
+            "initial_shark_life" => {
+                self.config.initial_shark_life = parse_range(value).map_err(invalid)?;
+            }
+            "topology" => self.topology = value.parse().map_err(|e| invalid(message_of(e)))?,
+            "neighbourhood" => {
+                self.neighbourhood = value.parse().map_err(|e| invalid(message_of(e)))?
+            }
+            "update_order" => {
+                self.update_order = value.parse().map_err(|e| invalid(message_of(e)))?
+            }
+            "extinction_policy" => {
+                self.extinction_policy = value.parse().map_err(|e| invalid(message_of(e)))?
+            }
+            "seed" => self.seed = Some(parse_number(value).map_err(invalid)?),
+            _ => {
+                return Err(error(
+                    number,
+                    indent,
+                    &format!("unknown header key `{key}`"),
+                ))
+            }
+        }
+        if self.keys.iter().any(|(name, _, _)| name == key) {
+            return Err(error(
+                number,
+                indent,
+                &format!("header key `{key}` is set twice"),
+            ));
+        }
+        self.keys.push((key.into(), number, column));
+        Ok(())
+    }
+
+    fn parse_row(&mut self, line: &str, number: usize) -> Result {
+        let y = self.rows;
+        let mut x = 0;
+        let mut glyphs = line.char_indices();
+        while let Some((index, glyph)) = glyphs.next() {
+            let r#type = match glyph {
+                'F' => FieldType::Fish,
+                'S' => FieldType::Shark,
+                '_' => FieldType::Plankton,
+                ',' => continue,
+                '\u{1b}' => {
+                    skip_colour(&mut glyphs);
+                    continue;
+                }
+                glyph if glyph.is_whitespace() => continue,
+                glyph => {
+                    return Err(error(
+                        number,
+                        column_of(line, index),
+                        &format!("unexpected character `{glyph}`, expected `F`, `S` or `_`"),
+                    ))
+                }
+            };
+
+            if self.columns.is_some_and(|columns| x >= columns) {
+                return Err(error(
+                    number,
+                    column_of(line, index),
+                    &format!("row is longer than the first row ({x} fields)"),
+                ));
+            }
+            if r#type != FieldType::Plankton {
+                self.animals.push(((x, y), r#type));
+            }
+            x += 1;
+        }
+
+        match self.columns {
+            _ if x == 0 => return Err(error(number, 1, "row contains no fields")),
+            Some(columns) if x < columns => {
+                return Err(error(
+                    number,
+                    column_of(line, line.trim_end().len()),
+                    &format!("row has {x} fields but the first row has {columns}"),
+                ))
+            }
+            None => self.columns = Some(x),
+            _ => (),
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn layout(&mut self) -> Result<Layout> {
+        match self.columns {
+            Some(columns) => Ok(Layout {
+                rows: self.rows,
+                columns,
+                animals: std::mem::take(&mut self.animals),
+            }),
+            None => Err(error(self.last_line + 1, 1, "the text contains no grid")),
+        }
+    }
+
+    fn finish(mut self) -> Result<Board> {
+        let layout = self.layout()?;
+        self.validate()?;
+
+        let mut board = Board::new(0, 0, layout.rows, layout.columns)
+            .with_config(self.config)?
+            .with_topology(self.topology)
+            .with_neighbourhood(self.neighbourhood)
+            .with_update_order(self.update_order)
+            .with_extinction_policy(self.extinction_policy);
+        if let Some(seed) = self.seed {
+            board = board.with_seed(seed);
+        }
+        board.place_animals(&layout.animals);
+        Ok(board)
+    }
+
+    fn validate(&self) -> Result {
+        if let Err(SimulationError::InvalidConfig { key, message }) = self.config.validate() {
+            // Point at the header line that set the invalid value
+            let (line, column) = self
+                .keys
+                .iter()
+                .find(|(name, _, _)| *name == key)
+                .map(|(_, line, column)| (*line, *column))
+                .unwrap_or((1, 1));
+            return Err(error(line, column, &format!("invalid `{key}`: {message}")));
+        }
+        Ok(())
+    }
+}
+
+/// Skips the rest of an ANSI escape sequence like `\u{1b}[32m` that sets the colour of a glyph
+fn skip_colour(glyphs: &mut impl Iterator<Item = (usize, char)>) {
+    if let Some((_, '[')) = glyphs.next() {
+        for (_, glyph) in glyphs.by_ref() {
+            if ('@'..='~').contains(&glyph) {
+                break;
+            }
+        }
+    }
+}
+
+fn error(line: usize, column: usize, message: &str) -> SimulationError {
+    SimulationError::ParseError {
+        line,
+        column,
+        message: message.into(),
+    }
+}
+
+/// Converts a byte offset of a line into a column counted in characters starting at 1
+fn column_of(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+pub(crate) fn message_of(error: SimulationError) -> String {
+    match error {
+        SimulationError::InvalidConfig { message, .. } => message,
+        error => error.to_string(),
+    }
+}
+
+/// Parses a range like `1..8`
+pub(crate) fn parse_range(value: &str) -> std::result::Result<Range<u32>, String> {
+    let (start, end) = value
+        .split_once("..")
+        .ok_or_else(|| format!("expected a range like `1..8`, found `{value}`"))?;
+    Ok(parse_number(start.trim())?..parse_number(end.trim())?)
+}
+
+fn parse_number<T: FromStr>(value: &str) -> std::result::Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a non-negative number, found `{value}`"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_error(text: &str) -> (usize, usize) {
+        match text.parse::<Board>() {
+            Err(SimulationError::ParseError { line, column, .. }) => (line, column),
+            result => panic!("expected a parse error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let board: Board = "F_S\n__F\n".parse().unwrap();
+        assert_eq!(board.count_animals(), (2, 1));
+        assert_eq!(board.statistics().records()[0].plankton, 3);
+        assert_eq!(board.config(), &SimulationConfig::default());
+    }
+
+    #[test]
+    fn test_parse_separated_glyphs() {
+        let separated: Board = "seed = 3\nF, _, S, \n_, _, F, \n".parse().unwrap();
+        let compact: Board = "seed = 3\nF_S\n__F".parse().unwrap();
+        assert_eq!(separated, compact);
+    }
+
+    #[test]
+    fn test_parse_display_output() {
+        let mut board = Board::new(30, 10, 8, 9).with_seed(4);
+        board.generate_random_animals();
+        let parsed: Board = board.to_string().parse().unwrap();
+        assert_eq!(parsed.to_string(), board.to_string());
+        assert_eq!(parsed.count_animals(), (30, 10));
+
+        // The glyphs are coloured if the output is a terminal
+        let coloured: Board = "seed = 3\n\u{1b}[32mF\u{1b}[0m, \u{1b}[34m_\u{1b}[0m, \n"
+            .parse()
+            .unwrap();
+        assert_eq!(coloured, "seed = 3\nF_".parse().unwrap());
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let board: Board = "
+            # Every parameter
+            fish_breed_time = 2
+            shark_breed_time = 4
+            shark_starvation_time = 5
+            initial_shark_life = 3..4
+            topology = box
+            neighbourhood = hexagonal
+            update_order = synchronous
+            extinction_policy = stop_on_any_extinct
+            seed = 9
+
+            _S
+            F_
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            board.config(),
+            &SimulationConfig {
+                fish_breed_time: 2,
+                shark_breed_time: 4,
+                shark_starvation_time: 5,
+                initial_shark_life: 3..4,
+            }
+        );
+        assert_eq!(board.topology(), Topology::Box);
+        assert_eq!(board.neighbourhood(), Neighbourhood::Hexagonal);
+        assert_eq!(board.update_order(), UpdateOrder::Synchronous);
+    }
+
+    #[test]
+    fn test_same_seed_same_board() {
+        let text = "seed = 5\n_F_\nFSF\n_F_";
+        let mut first: Board = text.parse().unwrap();
+        let mut second: Board = text.parse().unwrap();
+        for _ in 0..10 {
+            assert_eq!(first.step(), second.step());
+        }
+    }
+
+    #[test]
+    fn test_parse_builder() {
+        let text = "seed = 5\ntopology = box\nshark_breed_time = 4\n_F_\nFS_";
+        let builder: BoardBuilder = text.parse().unwrap();
+        assert_eq!(builder.clone().build(), text.parse::<Board>());
+
+        let board = builder.seed(6).build().unwrap();
+        assert_eq!(board.config().shark_breed_time, 4);
+        assert_eq!(board.count_animals(), (2, 1));
+        assert!(matches!(
+            "shark_breed_time = 0\nF".parse::<BoardBuilder>(),
+            Err(SimulationError::ParseError { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_error_positions() {
+        assert_eq!(parse_error("F_S\n_X_"), (2, 2));
+        assert_eq!(parse_error("F_S\n__"), (2, 3));
+        assert_eq!(parse_error("F_S\n____"), (2, 4));
+        assert_eq!(parse_error("seed = x\nF"), (1, 8));
+        assert_eq!(parse_error("  colour = red\nF"), (1, 3));
+        assert_eq!(parse_error("topology = sphere\nF"), (1, 12));
+        assert_eq!(parse_error("F\nseed = 1"), (2, 1));
+        assert_eq!(parse_error("# nothing\n"), (2, 1));
+        assert_eq!(parse_error("seed = 1\n  seed = 2\nF"), (2, 3));
+    }
+
+    #[test]
+    fn test_invalid_config_points_at_header() {
+        assert_eq!(parse_error("seed = 1\nshark_breed_time = 0\nF"), (2, 20));
+        assert_eq!(parse_error("initial_shark_life = 4..2\nF"), (1, 22));
+        assert_eq!(
+            parse_error("fish_breed_time = 2\nfish_breed_time = 0\nF"),
+            (2, 1)
+        );
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let board = Board::from_reader("FS".as_bytes()).unwrap();
+        assert_eq!(board.count_animals(), (1, 1));
+    }
+}
@@ -0,0 +1,131 @@
+//! Contains the report that is returned for every step of the simulation and the events it can
+//! contain.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, ExtinctionPolicy, SimulationEvent};
+//!
+//! let mut board = Board::new(10, 5, 25, 25)
+//!     .with_seed(42)
+//!     .with_extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+//! board.generate_random_animals();
+//!
+//! while let Ok(report) = board.step() {
+//!     if report.events.contains(&SimulationEvent::SharksExtinct) {
+//!         println!("Sharks died out in step {}", report.step);
+//!     }
+//! }
+//! ```
+use std::str::FromStr;
+
+use crate::{field::FieldType, Result, SimulationError};
+
+/// Something noteworthy that happened during a step
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SimulationEvent {
+    /// The last fish was eaten during the step
+    FishExtinct,
+    /// The last shark starved during the step
+    SharksExtinct,
+}
+
+/// The outcome of a single step of the simulation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepReport {
+    /// The number of the step that was simulated (starting at 1)
+    pub step: u32,
+    /// The amount of fishes after the step
+    pub fishes: u32,
+    /// The amount of sharks after the step
+    pub sharks: u32,
+    /// The amount of fields without an animal after the step
+    pub plankton: u32,
+    /// The amount of fishes that were born during the step
+    pub fish_births: u32,
+    /// The amount of sharks that were born during the step
+    pub shark_births: u32,
+    /// The amount of fishes that were eaten by sharks during the step
+    pub fishes_eaten: u32,
+    /// The amount of sharks that starved during the step
+    pub sharks_starved: u32,
+    /// The amount of fishes that moved to another field during the step
+    pub fish_moves: u32,
+    /// The amount of sharks that moved to another field during the step
+    pub shark_moves: u32,
+    /// The events that happened during the step
+    pub events: Vec<SimulationEvent>,
+}
+
+impl StepReport {
+    pub(crate) fn count_birth(&mut self, r#type: &FieldType) {
+        match r#type {
+            FieldType::Fish => self.fish_births += 1,
+            FieldType::Shark => self.shark_births += 1,
+            FieldType::Plankton => (),
+        }
+    }
+
+    pub(crate) fn count_move(&mut self, r#type: &FieldType) {
+        match r#type {
+            FieldType::Fish => self.fish_moves += 1,
+            FieldType::Shark => self.shark_moves += 1,
+            FieldType::Plankton => (),
+        }
+    }
+}
+
+/// Controls whether [`Board::step`](crate::Board::step) refuses to simulate once a species died out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtinctionPolicy {
+    /// Keep simulating the surviving population
+    #[default]
+    Continue,
+    /// Stop as soon as there are no fishes left
+    StopOnFishExtinct,
+    /// Stop as soon as there are no sharks left
+    StopOnSharksExtinct,
+    /// Stop as soon as one of the species died out
+    StopOnAnyExtinct,
+}
+
+impl ExtinctionPolicy {
+    /// Returns `true` if the simulation has to stop because there are no fishes left
+    pub fn stops_on_fish(&self) -> bool {
+        matches!(
+            self,
+            ExtinctionPolicy::StopOnFishExtinct | ExtinctionPolicy::StopOnAnyExtinct
+        )
+    }
+
+    /// Returns `true` if the simulation has to stop because there are no sharks left
+    pub fn stops_on_sharks(&self) -> bool {
+        matches!(
+            self,
+            ExtinctionPolicy::StopOnSharksExtinct | ExtinctionPolicy::StopOnAnyExtinct
+        )
+    }
+}
+
+impl FromStr for ExtinctionPolicy {
+    type Err = SimulationError;
+
+    /// Parses the snake case name of a policy, e.g. `stop_on_any_extinct`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "continue" => Ok(ExtinctionPolicy::Continue),
+            "stop_on_fish_extinct" => Ok(ExtinctionPolicy::StopOnFishExtinct),
+            "stop_on_sharks_extinct" => Ok(ExtinctionPolicy::StopOnSharksExtinct),
+            "stop_on_any_extinct" => Ok(ExtinctionPolicy::StopOnAnyExtinct),
+            _ => Err(SimulationError::InvalidConfig {
+                key: "extinction_policy".into(),
+                message: format!(
+                    "unknown extinction policy `{name}`, expected one of continue, \
+                     stop_on_fish_extinct, stop_on_sharks_extinct, stop_on_any_extinct"
+                ),
+            }),
+        }
+    }
+}
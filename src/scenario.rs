@@ -0,0 +1,561 @@
+//! Contains the scenario files that describe a complete simulation in TOML (requires the
+//! `scenario` feature).
+//!
+//! A scenario sets the board either with `rows`, `columns`, `fishes` and `sharks` or with an
+//! explicit `layout` in the plain-text board format. All other keys are optional:
+//!
+//! ```toml
+//! name = "Many sharks"
+//! seed = 42
+//! steps = 500
+//! rows = 40
+//! columns = 40
+//! fishes = 200
+//! sharks = 100
+//! topology = "torus"
+//! neighbourhood = "von_neumann"
+//! update_order = "sequential"
+//! extinction_policy = "stop_on_any_extinct"
+//!
+//! [config]
+//! fish_breed_time = 3
+//! shark_breed_time = 8
+//! shark_starvation_time = 8
+//! initial_shark_life = "1..8"
+//!
+//! [output]
+//! format = "csv"
+//! path = "many_sharks.csv"
+//! ```
+//!
+//! Invalid values are reported as [`SimulationError::ParseError`] with the line and column of
+//! the offending key.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Scenario;
+//!
+//! let scenario = Scenario::from_toml(
+//!     r#"
+//!     steps = 20
+//!     layout = """
+//!     _F_
+//!     FSF
+//!     _F_
+//!     """
+//!
+//!     [config]
+//!     shark_starvation_time = 2
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! let mut board = scenario.board().unwrap();
+//! for _ in 0..scenario.steps.unwrap() {
+//!     board.step().unwrap();
+//! }
+//! ```
+use std::{collections::BTreeMap, io::Read, ops::Range, path::PathBuf, str::FromStr};
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::{
+    parser::{message_of, parse_layout, parse_range},
+    Board, BoardBuilder, Result, SimulationConfig, SimulationError,
+};
+
+/// The format the statistics of a simulation are written in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// See [`CsvWriter`](crate::CsvWriter)
+    #[default]
+    Csv,
+    /// The records of the statistics as a JSON array
+    Json,
+    /// See [`MatlabScript`](crate::MatlabScript)
+    Matlab,
+}
+
+impl FromStr for OutputFormat {
+    type Err = SimulationError;
+
+    /// Parses the lower case name of a format, e.g. `matlab`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            "matlab" => Ok(OutputFormat::Matlab),
+            _ => Err(SimulationError::InvalidConfig {
+                key: "format".into(),
+                message: format!("unknown format `{name}`, expected one of csv, json, matlab"),
+            }),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Returns the usual file extension of the format without a dot, e.g. `m` for MATLAB
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+            OutputFormat::Matlab => "m",
+        }
+    }
+}
+
+/// Where and in which format the statistics of a scenario are written
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    /// The format of the statistics
+    pub format: OutputFormat,
+    /// The file the statistics are written to or `None` for the standard output
+    pub path: Option<PathBuf>,
+}
+
+/// A complete description of a simulation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scenario {
+    /// The name of the scenario
+    pub name: Option<String>,
+    /// The maximum amount of steps that are simulated
+    pub steps: Option<u32>,
+    /// Where the statistics are written
+    pub output: Option<Output>,
+    builder: BoardBuilder,
+}
+
+impl Scenario {
+    /// Parses a scenario from TOML
+    ///
+    /// # Arguments
+    /// * `text` - The content of a scenario file
+    ///
+    /// # Errors
+    /// * [`SimulationError::ParseError`] with the line and column of the first invalid key or
+    ///   value
+    pub fn from_toml(text: &str) -> Result<Scenario> {
+        let file: ScenarioFile = toml::from_str(text).map_err(|error| {
+            let span = error.span().unwrap_or(0..0);
+            error_at(text, span, error.message().trim())
+        })?;
+        file.into_scenario(text)
+    }
+
+    /// Reads a scenario in TOML
+    ///
+    /// # Arguments
+    /// * `reader` - The source of the text, e.g. a scenario file
+    ///
+    /// # Errors
+    /// * [`SimulationError::Io`] if reading fails
+    /// * [`SimulationError::ParseError`] if the text is not a valid scenario
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Scenario> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Self::from_toml(&text)
+    }
+
+    /// Returns the seed of the board or `None` if every board gets a random seed
+    pub fn seed(&self) -> Option<u64> {
+        self.builder.configured_seed()
+    }
+
+    /// Returns the builder that creates the board of the scenario
+    ///
+    /// The builder can be changed before the board is built, e.g. to override the seed.
+    pub fn builder(&self) -> &BoardBuilder {
+        &self.builder
+    }
+
+    /// Creates the board of the scenario
+    ///
+    /// Every call places the animals anew. With a seed the boards are identical.
+    ///
+    /// # Errors
+    /// See [`BoardBuilder::build`]
+    pub fn board(&self) -> Result<Board> {
+        self.builder.clone().build()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioFile {
+    name: Option<String>,
+    seed: Option<u64>,
+    steps: Option<u32>,
+    rows: Option<Spanned<u32>>,
+    columns: Option<Spanned<u32>>,
+    fishes: Option<Spanned<u32>>,
+    sharks: Option<Spanned<u32>>,
+    layout: Option<Spanned<String>>,
+    topology: Option<Spanned<String>>,
+    neighbourhood: Option<Spanned<String>>,
+    update_order: Option<Spanned<String>>,
+    extinction_policy: Option<Spanned<String>>,
+    #[serde(default)]
+    config: ConfigFile,
+    output: Option<OutputFile>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    fish_breed_time: Option<Spanned<u32>>,
+    shark_breed_time: Option<Spanned<u32>>,
+    shark_starvation_time: Option<Spanned<u32>>,
+    initial_shark_life: Option<Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutputFile {
+    format: Option<Spanned<String>>,
+    path: Option<PathBuf>,
+}
+
+impl ScenarioFile {
+    fn into_scenario(self, text: &str) -> Result<Scenario> {
+        let invalid = |key: &str, span: Range<usize>, message: &str| {
+            error_at(text, span, &format!("invalid `{key}`: {message}"))
+        };
+        let config = self.config(text)?;
+        let mut builder = BoardBuilder::default().config(config);
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed);
+        }
+        if let Some(topology) = &self.topology {
+            builder = builder.topology(parse_value(text, "topology", topology)?);
+        }
+        if let Some(neighbourhood) = &self.neighbourhood {
+            builder = builder.neighbourhood(parse_value(text, "neighbourhood", neighbourhood)?);
+        }
+        if let Some(update_order) = &self.update_order {
+            builder = builder.update_order(parse_value(text, "update_order", update_order)?);
+        }
+        if let Some(policy) = &self.extinction_policy {
+            builder = builder.extinction_policy(parse_value(text, "extinction_policy", policy)?);
+        }
+
+        let population = [
+            ("rows", &self.rows),
+            ("columns", &self.columns),
+            ("fishes", &self.fishes),
+            ("sharks", &self.sharks),
+        ];
+        if let Some(layout) = &self.layout {
+            if let Some((key, Some(value))) = population.iter().find(|(_, value)| value.is_some()) {
+                return Err(invalid(
+                    key,
+                    value.span(),
+                    "cannot be combined with `layout`",
+                ));
+            }
+            parse_layout(layout.get_ref())
+                .map_err(|error| layout_error(text, layout.span(), error))?;
+            builder = builder.layout(layout.get_ref());
+        } else {
+            let value = |key: &str| population.iter().find(|(name, _)| *name == key).unwrap().1;
+            let (rows, columns) = match (value("rows"), value("columns")) {
+                (Some(rows), Some(columns)) => (rows, columns),
+                _ => {
+                    return Err(error_at(
+                        text,
+                        0..0,
+                        "either `rows` and `columns` or `layout` have to be set",
+                    ))
+                }
+            };
+            for (key, size) in [("rows", rows), ("columns", columns)] {
+                if *size.get_ref() == 0 {
+                    return Err(invalid(key, size.span(), "must be greater than zero"));
+                }
+            }
+            if rows.get_ref().checked_mul(*columns.get_ref()).is_none() {
+                return Err(invalid(
+                    "rows",
+                    rows.span(),
+                    "the amount of fields does not fit into a u32",
+                ));
+            }
+
+            let fishes = value("fishes")
+                .as_ref()
+                .map_or(0, |fishes| *fishes.get_ref());
+            let sharks = value("sharks")
+                .as_ref()
+                .map_or(0, |sharks| *sharks.get_ref());
+            let fields = u64::from(*rows.get_ref()) * u64::from(*columns.get_ref());
+            if u64::from(fishes) + u64::from(sharks) > fields {
+                let (key, span) = match value("fishes") {
+                    Some(fishes) => ("fishes", fishes.span()),
+                    None => ("sharks", value("sharks").as_ref().unwrap().span()),
+                };
+                return Err(invalid(
+                    key,
+                    span,
+                    &format!("{fishes} fishes and {sharks} sharks do not fit on {fields} fields"),
+                ));
+            }
+            builder = builder
+                .dimensions(*rows.get_ref(), *columns.get_ref())
+                .fishes(fishes)
+                .sharks(sharks);
+        }
+
+        let output = match self.output {
+            Some(output) => Some(Output {
+                format: match &output.format {
+                    Some(format) => parse_value(text, "output.format", format)?,
+                    None => OutputFormat::default(),
+                },
+                path: output.path,
+            }),
+            None => None,
+        };
+
+        Ok(Scenario {
+            name: self.name,
+            steps: self.steps,
+            output,
+            builder,
+        })
+    }
+
+    /// Creates the config and points at the key of the first invalid parameter
+    fn config(&self, text: &str) -> Result<SimulationConfig> {
+        let mut config = SimulationConfig::default();
+        let mut spans = BTreeMap::new();
+        let values = [
+            ("fish_breed_time", &self.config.fish_breed_time),
+            ("shark_breed_time", &self.config.shark_breed_time),
+            ("shark_starvation_time", &self.config.shark_starvation_time),
+        ];
+        for (key, value) in values {
+            let Some(value) = value else {
+                continue;
+            };
+            spans.insert(key, value.span());
+            match key {
+                "fish_breed_time" => config.fish_breed_time = *value.get_ref(),
+                "shark_breed_time" => config.shark_breed_time = *value.get_ref(),
+                _ => config.shark_starvation_time = *value.get_ref(),
+            }
+        }
+        if let Some(life) = &self.config.initial_shark_life {
+            spans.insert("initial_shark_life", life.span());
+            config.initial_shark_life = parse_range(life.get_ref()).map_err(|message| {
+                error_at(
+                    text,
+                    life.span(),
+                    &format!("invalid `config.initial_shark_life`: {message}"),
+                )
+            })?;
+        }
+
+        match config.validate() {
+            Err(SimulationError::InvalidConfig { key, message }) => Err(error_at(
+                text,
+                spans.get(key.as_str()).cloned().unwrap_or(0..0),
+                &format!("invalid `config.{key}`: {message}"),
+            )),
+            result => result.map(|_| config),
+        }
+    }
+}
+
+/// Parses the name of a setting and points at the value if it is unknown
+fn parse_value<T: FromStr<Err = SimulationError>>(
+    text: &str,
+    key: &str,
+    value: &Spanned<String>,
+) -> Result<T> {
+    value.get_ref().parse().map_err(|error| {
+        error_at(
+            text,
+            value.span(),
+            &format!("invalid `{key}`: {}", message_of(error)),
+        )
+    })
+}
+
+/// Creates a parse error at the start of a span of the text
+fn error_at(text: &str, span: Range<usize>, message: &str) -> SimulationError {
+    let (line, column) = position(text, span.start);
+    SimulationError::ParseError {
+        line,
+        column,
+        message: message.into(),
+    }
+}
+
+/// Converts a byte offset into a line and a column counted in characters starting at 1
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Moves the position of an error in the layout to its position in the scenario file
+fn layout_error(text: &str, span: Range<usize>, error: SimulationError) -> SimulationError {
+    let SimulationError::ParseError {
+        line,
+        column,
+        message,
+    } = error
+    else {
+        return error;
+    };
+
+    // Skip the quotes and the line break after the quotes of a multi-line string
+    let literal = &text[span.clone()];
+    let mut start = span.start + 1;
+    if literal.starts_with("\"\"\"") || literal.starts_with("'''") {
+        start = span.start + 3;
+        let rest = &text[start..];
+        if rest.starts_with("\r\n") {
+            start += 2;
+        } else if rest.starts_with('\n') {
+            start += 1;
+        }
+    }
+
+    let (start_line, start_column) = position(text, start);
+    SimulationError::ParseError {
+        line: start_line + line - 1,
+        column: if line == 1 {
+            start_column + column - 1
+        } else {
+            column
+        },
+        message: format!("invalid `layout`: {message}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ExtinctionPolicy, Topology};
+
+    fn parse_error(text: &str) -> (usize, usize, String) {
+        match Scenario::from_toml(text) {
+            Err(SimulationError::ParseError {
+                line,
+                column,
+                message,
+            }) => (line, column, message),
+            result => panic!("expected a parse error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_random_scenario() {
+        let scenario = Scenario::from_toml(
+            r#"
+name = "Many sharks"
+seed = 7
+steps = 50
+rows = 5
+columns = 6
+fishes = 4
+sharks = 2
+topology = "box"
+extinction_policy = "stop_on_any_extinct"
+
+[config]
+fish_breed_time = 2
+initial_shark_life = "3..5"
+
+[output]
+format = "matlab"
+path = "many_sharks.m"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(scenario.name.as_deref(), Some("Many sharks"));
+        assert_eq!(scenario.steps, Some(50));
+        assert_eq!(
+            scenario.output,
+            Some(Output {
+                format: OutputFormat::Matlab,
+                path: Some("many_sharks.m".into())
+            })
+        );
+
+        let board = scenario.board().unwrap();
+        assert_eq!((board.rows(), board.columns()), (5, 6));
+        assert_eq!(board.count_animals(), (4, 2));
+        assert_eq!(board.topology(), Topology::Box);
+        assert_eq!(board.config().fish_breed_time, 2);
+        assert_eq!(board.config().initial_shark_life, 3..5);
+        assert_eq!(board, scenario.board().unwrap());
+        assert_eq!(scenario.seed(), Some(7));
+
+        let expected = Board::builder()
+            .dimensions(5, 6)
+            .fishes(4)
+            .sharks(2)
+            .seed(7)
+            .topology(Topology::Box)
+            .extinction_policy(ExtinctionPolicy::StopOnAnyExtinct)
+            .config(board.config().clone())
+            .build()
+            .unwrap();
+        assert_eq!(board, expected);
+    }
+
+    #[test]
+    fn test_layout_scenario() {
+        let scenario = Scenario::from_toml(
+            "layout = \"\"\"\n_F_\nFSF\n\"\"\"\n[config]\nshark_starvation_time = 2\n",
+        )
+        .unwrap();
+        assert_eq!(scenario.seed(), None);
+        let board = scenario.board().unwrap();
+        assert_eq!((board.rows(), board.columns()), (2, 3));
+        assert_eq!(board.count_animals(), (3, 1));
+        assert_eq!(board.config().shark_starvation_time, 2);
+    }
+
+    #[test]
+    fn test_errors_point_at_key() {
+        let (line, column, message) =
+            parse_error("rows = 5\ncolumns = 5\n[config]\nshark_breed_time = 0\n");
+        assert_eq!((line, column), (4, 20));
+        assert!(message.contains("config.shark_breed_time"));
+
+        let (line, column, _) = parse_error("rows = 5\ncolumns = 5\ntopology = \"sphere\"\n");
+        assert_eq!((line, column), (3, 12));
+        assert_eq!(parse_error("rows = 5\ncolumns = 0\n").0, 2);
+        assert_eq!(parse_error("columns = 65536\nrows = 65536\n").0, 2);
+        assert_eq!(parse_error("rows = 2\ncolumns = 2\nfishes = 5\n").0, 3);
+        assert_eq!(parse_error("rows = 5\ncolumns = 5\ncolour = 1\n").0, 3);
+        assert_eq!(parse_error("rows = \"five\"\ncolumns = 5\n").0, 1);
+        assert_eq!(parse_error("layout = \"F\"\nrows = 5\n").0, 2);
+        assert_eq!(parse_error("fishes = 5\n").0, 1);
+    }
+
+    #[test]
+    fn test_layout_errors_point_into_layout() {
+        let (line, column, _) = parse_error("seed = 1\nlayout = \"\"\"\nF_S\nFX_\n\"\"\"\n");
+        assert_eq!((line, column), (4, 2));
+        let (line, column, _) = parse_error("layout = \"F_X\"\n");
+        assert_eq!((line, column), (1, 13));
+    }
+
+    #[test]
+    fn test_example_scenarios() {
+        for text in [
+            include_str!("../examples/scenarios/many_sharks.toml"),
+            include_str!("../examples/scenarios/real.toml"),
+        ] {
+            let scenario = Scenario::from_toml(text).unwrap();
+            assert!(scenario.name.is_some());
+            scenario.board().unwrap();
+        }
+    }
+}
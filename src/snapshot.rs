@@ -0,0 +1,285 @@
+//! Contains the versioned snapshot format that is used to save a board and continue the
+//! simulation later. The snapshot contains the whole state of the board including the random
+//! number generator, so a restored board continues exactly like the original.
+//!
+//! This module is only available with the `serde` feature.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Board;
+//!
+//! let mut board = Board::new(10, 5, 25, 25).with_seed(42);
+//! board.generate_random_animals();
+//! board.step().unwrap();
+//!
+//! let json = board.to_json().unwrap();
+//! let mut restored = Board::from_json(&json).unwrap();
+//! assert_eq!(board.step(), restored.step());
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Result, SimulationError};
+
+/// The version of the snapshot format that is written by this version of the library
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot<B> {
+    version: u32,
+    board: B,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+impl Board {
+    /// Saves the board as a JSON snapshot
+    ///
+    /// # Errors
+    /// [`SimulationError::InvalidSnapshot`] if the board could not be serialized
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(&Self::snapshot(self)).map_err(invalid_snapshot)
+    }
+
+    /// Restores a board from a JSON snapshot
+    ///
+    /// # Errors
+    /// * [`SimulationError::UnsupportedSnapshotVersion`] if the snapshot was written by an
+    ///   incompatible version of the library
+    /// * [`SimulationError::InvalidSnapshot`] if the snapshot is malformed or inconsistent
+    pub fn from_json(json: &str) -> Result<Board> {
+        let Version { version } = serde_json::from_str(json).map_err(invalid_snapshot)?;
+        check_version(version)?;
+
+        let snapshot: Snapshot<Board> = serde_json::from_str(json).map_err(invalid_snapshot)?;
+        Self::restore(snapshot)
+    }
+
+    /// Saves the board as a compact binary snapshot
+    ///
+    /// # Errors
+    /// [`SimulationError::InvalidSnapshot`] if the board could not be serialized
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(&Self::snapshot(self)).map_err(invalid_snapshot)
+    }
+
+    /// Restores a board from a binary snapshot
+    ///
+    /// # Errors
+    /// * [`SimulationError::UnsupportedSnapshotVersion`] if the snapshot was written by an
+    ///   incompatible version of the library
+    /// * [`SimulationError::InvalidSnapshot`] if the snapshot is malformed or inconsistent
+    pub fn from_bytes(bytes: &[u8]) -> Result<Board> {
+        let version: u32 = bincode::deserialize(bytes).map_err(invalid_snapshot)?;
+        check_version(version)?;
+
+        let snapshot: Snapshot<Board> = bincode::deserialize(bytes).map_err(invalid_snapshot)?;
+        Self::restore(snapshot)
+    }
+
+    fn snapshot(board: &Board) -> Snapshot<&Board> {
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            board,
+        }
+    }
+
+    fn restore(snapshot: Snapshot<Board>) -> Result<Board> {
+        snapshot.board.validate()?;
+        Ok(snapshot.board)
+    }
+}
+
+fn check_version(version: u32) -> Result {
+    if version != SNAPSHOT_VERSION {
+        return Err(SimulationError::UnsupportedSnapshotVersion { version });
+    }
+    Ok(())
+}
+
+fn invalid_snapshot(error: impl std::fmt::Display) -> SimulationError {
+    SimulationError::InvalidSnapshot {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Neighbourhood, SimulationConfig, Topology, UpdateOrder};
+
+    fn create_board() -> Board {
+        let config = SimulationConfig {
+            fish_breed_time: 2,
+            shark_breed_time: 5,
+            ..SimulationConfig::default()
+        };
+        let mut board = Board::new(40, 12, 9, 13)
+            .with_seed(11)
+            .with_config(config)
+            .unwrap()
+            .with_topology(Topology::HorizontalCylinder)
+            .with_neighbourhood(Neighbourhood::Moore)
+            .with_update_order(UpdateOrder::Shuffled)
+            .with_lineage_log();
+        board.generate_random_animals();
+        for _ in 0..7 {
+            board.step().unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn test_json_restore_continues_identically() {
+        let mut board = create_board();
+        let mut restored = Board::from_json(&board.to_json().unwrap()).unwrap();
+        assert_eq!(board, restored);
+
+        for _ in 0..20 {
+            assert_eq!(board.step(), restored.step());
+        }
+        assert_eq!(board, restored);
+    }
+
+    #[test]
+    fn test_binary_restore_continues_identically() {
+        let mut board = create_board();
+        let bytes = board.to_bytes().unwrap();
+        assert!(bytes.len() < board.to_json().unwrap().len());
+
+        let mut restored = Board::from_bytes(&bytes).unwrap();
+        for _ in 0..20 {
+            assert_eq!(board.step(), restored.step());
+        }
+        assert_eq!(board, restored);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let json = create_board().to_json().unwrap().replacen(
+            &format!("\"version\":{SNAPSHOT_VERSION}"),
+            "\"version\":999",
+            1,
+        );
+        assert_eq!(
+            Board::from_json(&json),
+            Err(SimulationError::UnsupportedSnapshotVersion { version: 999 })
+        );
+
+        let mut bytes = create_board().to_bytes().unwrap();
+        bytes[0] = 0;
+        assert_eq!(
+            Board::from_bytes(&bytes),
+            Err(SimulationError::UnsupportedSnapshotVersion { version: 0 })
+        );
+    }
+
+    #[test]
+    fn test_malformed_snapshot() {
+        assert!(matches!(
+            Board::from_json("{\"version\":1}"),
+            Err(SimulationError::InvalidSnapshot { .. })
+        ));
+        assert!(matches!(
+            Board::from_bytes(&[1, 0]),
+            Err(SimulationError::InvalidSnapshot { .. })
+        ));
+    }
+
+    /// Restores the snapshot of [`create_board`] after changing its JSON
+    fn restore_changed(change: impl FnOnce(&mut serde_json::Value)) -> Result<Board> {
+        let json = create_board().to_json().unwrap();
+        let mut snapshot: serde_json::Value = serde_json::from_str(&json).unwrap();
+        change(&mut snapshot["board"]);
+        Board::from_json(&snapshot.to_string())
+    }
+
+    /// Returns the status of the first field of a type
+    fn first_status<'a>(
+        board: &'a mut serde_json::Value,
+        r#type: &str,
+    ) -> &'a mut serde_json::Value {
+        board["fields"]
+            .as_array_mut()
+            .unwrap()
+            .iter_mut()
+            .flat_map(|row| row.as_array_mut().unwrap().iter_mut())
+            .find(|field| field["type"] == r#type)
+            .map(|field| &mut field["status"])
+            .unwrap()
+    }
+
+    fn assert_invalid(result: Result<Board>) {
+        assert!(
+            matches!(result, Err(SimulationError::InvalidSnapshot { .. })),
+            "expected an invalid snapshot, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_status_must_match_type() {
+        assert!(restore_changed(|_| ()).is_ok());
+        assert_invalid(restore_changed(|board| {
+            first_status(board, "Fish")["life"] = 3.into();
+        }));
+        assert_invalid(restore_changed(|board| {
+            first_status(board, "Shark")["life"] = serde_json::Value::Null;
+        }));
+        assert_invalid(restore_changed(|board| {
+            *first_status(board, "Plankton") = first_status(board, "Fish").clone();
+        }));
+    }
+
+    #[test]
+    fn test_shark_without_life() {
+        assert_invalid(restore_changed(|board| {
+            first_status(board, "Shark")["life"] = 0.into();
+        }));
+    }
+
+    #[test]
+    fn test_next_id_above_every_id() {
+        assert_invalid(restore_changed(|board| {
+            board["next_id"] = 1.into();
+        }));
+    }
+
+    #[test]
+    fn test_ids_are_unique() {
+        assert_invalid(restore_changed(|board| {
+            let id = first_status(board, "Fish")["id"].clone();
+            first_status(board, "Shark")["id"] = id;
+        }));
+    }
+
+    #[test]
+    fn test_population_fits_on_board() {
+        assert_invalid(restore_changed(|board| {
+            board["amount_fishes"] = 100.into();
+            board["amount_sharks"] = 100.into();
+        }));
+    }
+
+    #[test]
+    fn test_lineage_without_cycles() {
+        assert_invalid(restore_changed(|board| {
+            let records = &mut board["lineage"]["records"];
+            records["1"]["parent_id"] = 2.into();
+            records["2"]["parent_id"] = 1.into();
+        }));
+    }
+
+    #[test]
+    fn test_inconsistent_snapshot() {
+        let json = create_board()
+            .to_json()
+            .unwrap()
+            .replacen("\"rows\":9", "\"rows\":10", 1);
+        assert!(matches!(
+            Board::from_json(&json),
+            Err(SimulationError::InvalidSnapshot { .. })
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+//! Contains the statistics a board records for every step of the simulation.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::Board;
+//!
+//! let mut board = Board::new(10, 5, 25, 25).with_seed(42);
+//! board.generate_random_animals();
+//! for _ in 0..10 {
+//!     board.step().unwrap();
+//! }
+//!
+//! // The initial state and one record per step
+//! assert_eq!(board.statistics().len(), 11);
+//! for record in board.statistics() {
+//!     println!("{}: {} fishes, {} sharks", record.step, record.fishes, record.sharks);
+//! }
+//! ```
+//!
+//! Besides the time series the statistics describe the ages of the animals:
+//! ```
+//! use planetensimulation::Board;
+//!
+//! let mut board = Board::new(100, 20, 25, 25).with_seed(42);
+//! board.generate_random_animals();
+//! for _ in 0..50 {
+//!     board.step().unwrap();
+//! }
+//!
+//! let statistics = board.statistics();
+//! println!("Oldest fish: {:?}", statistics.fish_ages().max_age());
+//! println!("Mean lifespan of a shark: {:?}", statistics.shark_lifespans().mean());
+//! ```
+use std::slice::Iter;
+
+use crate::{Field, FieldType, StepReport};
+
+/// Histogram of the ages of animals
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgeDistribution {
+    counts: Vec<u32>,
+}
+
+impl AgeDistribution {
+    pub(crate) fn add(&mut self, age: u32) {
+        let age = age as usize;
+        if self.counts.len() <= age {
+            self.counts.resize(age + 1, 0);
+        }
+        self.counts[age] += 1;
+    }
+
+    /// Returns the amount of animals for every age starting at zero
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// Returns the amount of animals with the given age
+    pub fn count(&self, age: u32) -> u32 {
+        self.counts.get(age as usize).copied().unwrap_or(0)
+    }
+
+    /// Returns the amount of animals in the distribution
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the highest age or `None` if the distribution is empty
+    pub fn max_age(&self) -> Option<u32> {
+        self.counts
+            .iter()
+            .rposition(|count| *count > 0)
+            .map(|age| age as u32)
+    }
+
+    /// Returns the mean age or `None` if the distribution is empty
+    pub fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        let sum: f64 = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(age, count)| age as f64 * f64::from(*count))
+            .sum();
+        Some(sum / f64::from(total))
+    }
+}
+
+/// Time series of the [`StepReport`]s of a board
+///
+/// The first record describes the initial state of the board and contains no births, deaths
+/// or moves.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    records: Vec<StepReport>,
+    fish_ages: AgeDistribution,
+    shark_ages: AgeDistribution,
+    fish_lifespans: AgeDistribution,
+    shark_lifespans: AgeDistribution,
+}
+
+impl Statistics {
+    pub(crate) fn record(&mut self, report: &StepReport) {
+        self.records.push(report.clone());
+    }
+
+    /// Replaces the age distributions of the living animals
+    pub(crate) fn record_ages<'a>(&mut self, fields: impl Iterator<Item = &'a Field>) {
+        self.fish_ages = AgeDistribution::default();
+        self.shark_ages = AgeDistribution::default();
+        for field in fields {
+            let Some(status) = &field.status else {
+                continue;
+            };
+            match field.r#type {
+                FieldType::Fish => self.fish_ages.add(status.age()),
+                FieldType::Shark => self.shark_ages.add(status.age()),
+                FieldType::Plankton => (),
+            }
+        }
+    }
+
+    /// Adds the age of an animal that was eaten or starved to the lifespans
+    pub(crate) fn record_death(&mut self, animal: &Field) {
+        let Some(status) = &animal.status else {
+            return;
+        };
+        match animal.r#type {
+            FieldType::Fish => self.fish_lifespans.add(status.age()),
+            FieldType::Shark => self.shark_lifespans.add(status.age()),
+            FieldType::Plankton => (),
+        }
+    }
+
+    /// Returns all records ordered by step
+    pub fn records(&self) -> &[StepReport] {
+        &self.records
+    }
+
+    /// Returns an iterator over all records ordered by step
+    pub fn iter(&self) -> Iter<'_, StepReport> {
+        self.records.iter()
+    }
+
+    /// Returns the amount of records
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if nothing was recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the record of the latest step
+    pub fn last(&self) -> Option<&StepReport> {
+        self.records.last()
+    }
+
+    /// Returns the amount of fishes for every step
+    pub fn fishes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.records.iter().map(|record| record.fishes)
+    }
+
+    /// Returns the amount of sharks for every step
+    pub fn sharks(&self) -> impl Iterator<Item = u32> + '_ {
+        self.records.iter().map(|record| record.sharks)
+    }
+
+    /// Returns the ages of the fishes that are alive after the latest step
+    pub fn fish_ages(&self) -> &AgeDistribution {
+        &self.fish_ages
+    }
+
+    /// Returns the ages of the sharks that are alive after the latest step
+    pub fn shark_ages(&self) -> &AgeDistribution {
+        &self.shark_ages
+    }
+
+    /// Returns the ages at which fishes were eaten
+    ///
+    /// The age of a fish is taken from the start of the step in which it was eaten, whatever the
+    /// update order. Fishes that are removed with [`Board::place`](crate::Board::place) or
+    /// [`Board::clear`](crate::Board::clear) are not counted.
+    pub fn fish_lifespans(&self) -> &AgeDistribution {
+        &self.fish_lifespans
+    }
+
+    /// Returns the ages at which sharks starved
+    ///
+    /// The age of a shark is taken from the start of the step in which it starved. Sharks that
+    /// are removed with [`Board::place`](crate::Board::place) or
+    /// [`Board::clear`](crate::Board::clear) are not counted.
+    pub fn shark_lifespans(&self) -> &AgeDistribution {
+        &self.shark_lifespans
+    }
+}
+
+impl<'a> IntoIterator for &'a Statistics {
+    type Item = &'a StepReport;
+    type IntoIter = Iter<'a, StepReport>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_age_distribution() {
+        let mut distribution = AgeDistribution::default();
+        assert_eq!(distribution.mean(), None);
+        assert_eq!(distribution.max_age(), None);
+
+        for age in [2, 4, 4, 6] {
+            distribution.add(age);
+        }
+        assert_eq!(distribution.counts(), &[0, 0, 1, 0, 2, 0, 1]);
+        assert_eq!(distribution.count(4), 2);
+        assert_eq!(distribution.count(10), 0);
+        assert_eq!(distribution.total(), 4);
+        assert_eq!(distribution.max_age(), Some(6));
+        assert_eq!(distribution.mean(), Some(4.0));
+    }
+}
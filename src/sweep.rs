@@ -0,0 +1,743 @@
+//! Contains a runner that simulates a board for every combination of parameters in parallel.
+//!
+//! Every combination of breed times and initial populations is simulated once per repeat on
+//! its own board. The initial populations are given as amounts of animals or as densities, i.e.
+//! the share of the fields of the board that are occupied by a species. The boards are
+//! distributed over all CPU cores. Repeat `n` of every combination uses the seed `seed + n`, so
+//! the combinations are compared on the same random numbers and a sweep with a seed is
+//! reproducible.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, Sweep};
+//!
+//! let table = Sweep::new(Board::builder().dimensions(20, 20).fishes(80).sharks(10))
+//!     .fish_breed_times(2..=4)
+//!     .shark_breed_times([6, 8])
+//!     .shark_densities([0.05])
+//!     .repeats(3)
+//!     .steps(50)
+//!     .seed(42)
+//!     .run()
+//!     .unwrap();
+//!
+//! // One run per combination and repeat, one summary per combination
+//! assert_eq!(table.runs().len(), 18);
+//! for summary in table.summaries() {
+//!     println!(
+//!         "{:?}: {} of {} runs without sharks",
+//!         summary.parameters, summary.sharks_extinctions, summary.runs
+//!     );
+//!     assert_eq!(summary.parameters.sharks, 20);
+//! }
+//! ```
+use std::{
+    io::{self, Write},
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{Board, BoardBuilder, Result, SimulationError};
+
+const RUN_HEADER: &str = "fish_breed_time,shark_breed_time,fishes,sharks,seed,steps,\
+                          fish_extinct_step,sharks_extinct_step,fish_mean,fish_variance,\
+                          shark_mean,shark_variance,oscillation_period";
+const SUMMARY_HEADER: &str = "fish_breed_time,shark_breed_time,fishes,sharks,runs,\
+                              fish_extinctions,sharks_extinctions,mean_fish_extinct_step,\
+                              mean_sharks_extinct_step,fish_mean,fish_variance,shark_mean,\
+                              shark_variance,oscillation_period";
+
+/// Simulates a board for every combination of parameters
+#[derive(Clone, Debug)]
+pub struct Sweep {
+    builder: BoardBuilder,
+    fish_breed_times: Vec<u32>,
+    shark_breed_times: Vec<u32>,
+    fishes: Population,
+    sharks: Population,
+    repeats: u32,
+    steps: u32,
+    seed: Option<u64>,
+    threads: Option<usize>,
+}
+
+/// The parameters that differ between the runs of a sweep
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepParameters {
+    /// The amount of steps after which a fish breeds
+    pub fish_breed_time: u32,
+    /// The amount of steps after which a shark breeds
+    pub shark_breed_time: u32,
+    /// The amount of fishes at the start
+    pub fishes: u32,
+    /// The amount of sharks at the start
+    pub sharks: u32,
+}
+
+/// The outcome of a single simulation of a sweep
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepRun {
+    /// The parameters of the board
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub parameters: SweepParameters,
+    /// The seed of the board
+    pub seed: u64,
+    /// The amount of simulated steps
+    pub steps: u32,
+    /// The first step without fishes
+    pub fish_extinct_step: Option<u32>,
+    /// The first step without sharks
+    pub sharks_extinct_step: Option<u32>,
+    /// The mean amount of fishes over all steps
+    pub fish_mean: f64,
+    /// The variance of the amount of fishes over all steps
+    pub fish_variance: f64,
+    /// The mean amount of sharks over all steps
+    pub shark_mean: f64,
+    /// The variance of the amount of sharks over all steps
+    pub shark_variance: f64,
+    /// The period of the fish population in steps or `None` if it does not oscillate
+    pub oscillation_period: Option<u32>,
+}
+
+/// The outcomes of all repeats of one combination of parameters
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepSummary {
+    /// The parameters of the boards
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub parameters: SweepParameters,
+    /// The amount of runs
+    pub runs: u32,
+    /// The amount of runs in which the fishes died out
+    pub fish_extinctions: u32,
+    /// The amount of runs in which the sharks died out
+    pub sharks_extinctions: u32,
+    /// The mean step in which the fishes died out, over the runs in which they did
+    pub mean_fish_extinct_step: Option<f64>,
+    /// The mean step in which the sharks died out, over the runs in which they did
+    pub mean_sharks_extinct_step: Option<f64>,
+    /// The mean of [`SweepRun::fish_mean`]
+    pub fish_mean: f64,
+    /// The mean of [`SweepRun::fish_variance`]
+    pub fish_variance: f64,
+    /// The mean of [`SweepRun::shark_mean`]
+    pub shark_mean: f64,
+    /// The mean of [`SweepRun::shark_variance`]
+    pub shark_variance: f64,
+    /// The mean period over the runs that oscillate
+    pub oscillation_period: Option<f64>,
+}
+
+/// The runs of a sweep in the order of their parameters and repeats
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepTable {
+    runs: Vec<SweepRun>,
+}
+
+/// The initial populations of a species that are simulated
+#[derive(Clone, Debug)]
+enum Population {
+    Amounts(Vec<u32>),
+    Densities(Vec<f64>),
+}
+
+/// A single board that is simulated by a worker
+struct Job {
+    fish_breed_time: Option<u32>,
+    shark_breed_time: Option<u32>,
+    fishes: Option<u32>,
+    sharks: Option<u32>,
+    seed: u64,
+}
+
+impl Sweep {
+    /// Creates a sweep that simulates a single board of the builder for 100 steps
+    ///
+    /// # Arguments
+    /// * `builder` - Describes the board, its parameters are used unless the sweep varies them
+    pub fn new(builder: BoardBuilder) -> Self {
+        Sweep {
+            builder,
+            fish_breed_times: vec![],
+            shark_breed_times: vec![],
+            fishes: Population::Amounts(vec![]),
+            sharks: Population::Amounts(vec![]),
+            repeats: 1,
+            steps: 100,
+            seed: None,
+            threads: None,
+        }
+    }
+
+    /// Sets the breed times of the fishes that are simulated
+    pub fn fish_breed_times(mut self, times: impl IntoIterator<Item = u32>) -> Self {
+        self.fish_breed_times = times.into_iter().collect();
+        self
+    }
+
+    /// Sets the breed times of the sharks that are simulated
+    pub fn shark_breed_times(mut self, times: impl IntoIterator<Item = u32>) -> Self {
+        self.shark_breed_times = times.into_iter().collect();
+        self
+    }
+
+    /// Sets the initial amounts of fishes that are simulated
+    pub fn fishes(mut self, amounts: impl IntoIterator<Item = u32>) -> Self {
+        self.fishes = Population::Amounts(amounts.into_iter().collect());
+        self
+    }
+
+    /// Sets the initial amounts of sharks that are simulated
+    pub fn sharks(mut self, amounts: impl IntoIterator<Item = u32>) -> Self {
+        self.sharks = Population::Amounts(amounts.into_iter().collect());
+        self
+    }
+
+    /// Sets the initial densities of fishes that are simulated instead of amounts
+    ///
+    /// # Arguments
+    /// * `densities` - The shares of the fields that are occupied by fishes from `0.0` to `1.0`,
+    ///   rounded to the nearest amount of fishes
+    pub fn fish_densities(mut self, densities: impl IntoIterator<Item = f64>) -> Self {
+        self.fishes = Population::Densities(densities.into_iter().collect());
+        self
+    }
+
+    /// Sets the initial densities of sharks that are simulated instead of amounts
+    ///
+    /// # Arguments
+    /// * `densities` - The shares of the fields that are occupied by sharks from `0.0` to `1.0`,
+    ///   rounded to the nearest amount of sharks
+    pub fn shark_densities(mut self, densities: impl IntoIterator<Item = f64>) -> Self {
+        self.sharks = Population::Densities(densities.into_iter().collect());
+        self
+    }
+
+    /// Sets how often every combination is simulated with a different seed
+    pub fn repeats(mut self, repeats: u32) -> Self {
+        self.repeats = repeats;
+        self
+    }
+
+    /// Sets the maximum amount of steps of every run
+    ///
+    /// A run ends earlier if the extinction policy of the board stops it.
+    pub fn steps(mut self, steps: u32) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the seed of the first repeat, a random seed is used if it is not set
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the amount of threads, all CPU cores are used if it is not set
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Simulates all runs
+    ///
+    /// # Errors
+    /// * [`SimulationError::InvalidConfig`] if a density is not between `0.0` and `1.0` or the
+    ///   populations are varied although the builder has a layout, no board is simulated in
+    ///   these cases
+    /// * The first error of [`BoardBuilder::build`], e.g. if a combination has more animals than
+    ///   fields
+    pub fn run(&self) -> Result<SweepTable> {
+        let fishes = self
+            .fishes
+            .amounts(["fishes", "fish_densities"], &self.builder)?;
+        let sharks = self
+            .sharks
+            .amounts(["sharks", "shark_densities"], &self.builder)?;
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let mut jobs = vec![];
+        for fish_breed_time in optional(&self.fish_breed_times) {
+            for shark_breed_time in optional(&self.shark_breed_times) {
+                for fishes in optional(&fishes) {
+                    for sharks in optional(&sharks) {
+                        for repeat in 0..self.repeats {
+                            jobs.push(Job {
+                                fish_breed_time,
+                                shark_breed_time,
+                                fishes,
+                                sharks,
+                                seed: seed.wrapping_add(u64::from(repeat)),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let runs = run_parallel(&jobs, self.threads, |job| self.simulate(job))
+            .into_iter()
+            .collect::<Result<_>>()?;
+        Ok(SweepTable { runs })
+    }
+
+    fn simulate(&self, job: &Job) -> Result<SweepRun> {
+        let mut builder = self.builder.clone().seed(job.seed);
+        if let Some(time) = job.fish_breed_time {
+            builder = builder.fish_breed_time(time);
+        }
+        if let Some(time) = job.shark_breed_time {
+            builder = builder.shark_breed_time(time);
+        }
+        if let Some(fishes) = job.fishes {
+            builder = builder.fishes(fishes);
+        }
+        if let Some(sharks) = job.sharks {
+            builder = builder.sharks(sharks);
+        }
+
+        let mut board = builder.build()?;
+        for _ in 0..self.steps {
+            if board.step().is_err() {
+                break;
+            }
+        }
+        Ok(SweepRun::new(&board, job.seed))
+    }
+}
+
+impl Population {
+    /// Converts the population into amounts of animals on the board of a builder
+    ///
+    /// # Arguments
+    /// * `keys` - The names of the amounts and of the densities in errors
+    /// * `builder` - Describes the board
+    fn amounts(
+        &self,
+        [amounts_key, densities_key]: [&str; 2],
+        builder: &BoardBuilder,
+    ) -> Result<Vec<u32>> {
+        let (key, is_empty) = match self {
+            Population::Amounts(amounts) => (amounts_key, amounts.is_empty()),
+            Population::Densities(densities) => (densities_key, densities.is_empty()),
+        };
+        if builder.has_layout() && !is_empty {
+            return Err(SimulationError::InvalidConfig {
+                key: key.into(),
+                message: "cannot be varied because the layout places the animals".into(),
+            });
+        }
+
+        let fields = builder.amount_fields();
+        match self {
+            Population::Amounts(amounts) => Ok(amounts.clone()),
+            Population::Densities(densities) => densities
+                .iter()
+                .map(|density| {
+                    if !(0.0..=1.0).contains(density) {
+                        return Err(SimulationError::InvalidConfig {
+                            key: densities_key.into(),
+                            message: format!("{density} must be between 0 and 1"),
+                        });
+                    }
+                    Ok((density * fields as f64).round() as u32)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Turns an empty list of values into a list that keeps the value of the builder
+fn optional(values: &[u32]) -> Vec<Option<u32>> {
+    if values.is_empty() {
+        return vec![None];
+    }
+    values.iter().copied().map(Some).collect()
+}
+
+/// Calls a function for every item on a pool of threads and returns the results in order
+pub(crate) fn run_parallel<T: Sync, R: Send>(
+    items: &[T],
+    threads: Option<usize>,
+    function: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let threads = threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            return results;
+                        };
+                        results.push((index, function(item)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("a worker thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+impl SweepRun {
+    fn new(board: &Board, seed: u64) -> Self {
+        let statistics = board.statistics();
+        let fishes: Vec<u32> = statistics.fishes().collect();
+        let sharks: Vec<u32> = statistics.sharks().collect();
+        let extinct = |counts: &[u32]| {
+            counts
+                .iter()
+                .position(|count| *count == 0)
+                .map(|step| step as u32)
+        };
+        let (fish_mean, fish_variance) = mean_and_variance(&fishes);
+        let (shark_mean, shark_variance) = mean_and_variance(&sharks);
+
+        SweepRun {
+            parameters: SweepParameters {
+                fish_breed_time: board.config().fish_breed_time,
+                shark_breed_time: board.config().shark_breed_time,
+                fishes: fishes.first().copied().unwrap_or(0),
+                sharks: sharks.first().copied().unwrap_or(0),
+            },
+            seed,
+            steps: board.current_step(),
+            fish_extinct_step: extinct(&fishes),
+            sharks_extinct_step: extinct(&sharks),
+            fish_mean,
+            fish_variance,
+            shark_mean,
+            shark_variance,
+            oscillation_period: oscillation_period(&fishes),
+        }
+    }
+}
+
+impl SweepTable {
+    /// Returns all runs ordered by their parameters and repeats
+    pub fn runs(&self) -> &[SweepRun] {
+        &self.runs
+    }
+
+    /// Combines the repeats of every combination of parameters
+    pub fn summaries(&self) -> Vec<SweepSummary> {
+        let mut groups: Vec<(SweepParameters, Vec<&SweepRun>)> = vec![];
+        for run in &self.runs {
+            match groups
+                .iter_mut()
+                .find(|(parameters, _)| *parameters == run.parameters)
+            {
+                Some((_, runs)) => runs.push(run),
+                None => groups.push((run.parameters, vec![run])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(parameters, runs)| SweepSummary::new(parameters, &runs))
+            .collect()
+    }
+
+    /// Writes one CSV row per run
+    ///
+    /// # Errors
+    /// If a row could not be written
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{RUN_HEADER}")?;
+        for run in &self.runs {
+            let parameters = &run.parameters;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                parameters.fish_breed_time,
+                parameters.shark_breed_time,
+                parameters.fishes,
+                parameters.sharks,
+                run.seed,
+                run.steps,
+                optional_cell(run.fish_extinct_step),
+                optional_cell(run.sharks_extinct_step),
+                run.fish_mean,
+                run.fish_variance,
+                run.shark_mean,
+                run.shark_variance,
+                optional_cell(run.oscillation_period)
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Writes one CSV row per combination of parameters (see [`SweepTable::summaries`])
+    ///
+    /// # Errors
+    /// If a row could not be written
+    pub fn write_summary_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{SUMMARY_HEADER}")?;
+        for summary in self.summaries() {
+            let parameters = &summary.parameters;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                parameters.fish_breed_time,
+                parameters.shark_breed_time,
+                parameters.fishes,
+                parameters.sharks,
+                summary.runs,
+                summary.fish_extinctions,
+                summary.sharks_extinctions,
+                optional_cell(summary.mean_fish_extinct_step),
+                optional_cell(summary.mean_sharks_extinct_step),
+                summary.fish_mean,
+                summary.fish_variance,
+                summary.shark_mean,
+                summary.shark_variance,
+                optional_cell(summary.oscillation_period)
+            )?;
+        }
+        writer.flush()
+    }
+}
+
+impl SweepSummary {
+    fn new(parameters: SweepParameters, runs: &[&SweepRun]) -> Self {
+        let mean = |values: Vec<f64>| {
+            if values.is_empty() {
+                return None;
+            }
+            Some(values.iter().sum::<f64>() / values.len() as f64)
+        };
+        let collect = |value: fn(&SweepRun) -> Option<f64>| {
+            runs.iter().filter_map(|run| value(run)).collect::<Vec<_>>()
+        };
+        let fish_extinct_steps = collect(|run| run.fish_extinct_step.map(f64::from));
+        let sharks_extinct_steps = collect(|run| run.sharks_extinct_step.map(f64::from));
+
+        SweepSummary {
+            parameters,
+            runs: runs.len() as u32,
+            fish_extinctions: fish_extinct_steps.len() as u32,
+            sharks_extinctions: sharks_extinct_steps.len() as u32,
+            mean_fish_extinct_step: mean(fish_extinct_steps),
+            mean_sharks_extinct_step: mean(sharks_extinct_steps),
+            fish_mean: mean(collect(|run| Some(run.fish_mean))).unwrap_or(0.0),
+            fish_variance: mean(collect(|run| Some(run.fish_variance))).unwrap_or(0.0),
+            shark_mean: mean(collect(|run| Some(run.shark_mean))).unwrap_or(0.0),
+            shark_variance: mean(collect(|run| Some(run.shark_variance))).unwrap_or(0.0),
+            oscillation_period: mean(collect(|run| run.oscillation_period.map(f64::from))),
+        }
+    }
+}
+
+fn optional_cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Returns the mean and the population variance of a time series
+pub(crate) fn mean_and_variance(values: &[u32]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let length = values.len() as f64;
+    let mean = values.iter().map(|value| f64::from(*value)).sum::<f64>() / length;
+    let variance = values
+        .iter()
+        .map(|value| (f64::from(*value) - mean).powi(2))
+        .sum::<f64>()
+        / length;
+    (mean, variance)
+}
+
+/// Estimates the period of a time series from its autocorrelation
+///
+/// The period is the lag of the first maximum of the autocorrelation after it became negative.
+/// Returns `None` if there is no such maximum within half the length of the series.
+pub(crate) fn oscillation_period(values: &[u32]) -> Option<u32> {
+    let (mean, variance) = mean_and_variance(values);
+    if variance == 0.0 {
+        return None;
+    }
+    let deviations: Vec<f64> = values
+        .iter()
+        .map(|value| f64::from(*value) - mean)
+        .collect();
+    let autocorrelation = |lag: usize| {
+        deviations
+            .iter()
+            .zip(&deviations[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            / (deviations.len() as f64 * variance)
+    };
+
+    let mut negative = false;
+    let mut previous = 1.0;
+    for lag in 1..=deviations.len() / 2 {
+        let value = autocorrelation(lag);
+        if value < 0.0 {
+            negative = true;
+        } else if negative && previous > 0.0 && value < previous {
+            return Some(lag as u32 - 1);
+        }
+        previous = value;
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ExtinctionPolicy;
+
+    #[test]
+    fn test_every_combination_is_simulated() {
+        let sweep = Sweep::new(Board::builder().dimensions(10, 10).fishes(20).sharks(5))
+            .fish_breed_times([2, 3])
+            .sharks([5, 10])
+            .repeats(3)
+            .steps(20)
+            .seed(7)
+            .threads(3);
+        let table = sweep.run().unwrap();
+
+        assert_eq!(table.runs().len(), 12);
+        let seeds: Vec<u64> = table.runs().iter().take(3).map(|run| run.seed).collect();
+        assert_eq!(seeds, vec![7, 8, 9]);
+        assert_eq!(table.runs()[3].parameters.sharks, 10);
+        assert_eq!(table.runs()[6].parameters.fish_breed_time, 3);
+
+        let summaries = table.summaries();
+        assert_eq!(summaries.len(), 4);
+        assert!(summaries.iter().all(|summary| summary.runs == 3));
+
+        // The result does not depend on the amount of threads
+        assert_eq!(table, sweep.threads(1).run().unwrap());
+    }
+
+    #[test]
+    fn test_run_matches_single_board() {
+        let builder = Board::builder()
+            .dimensions(8, 8)
+            .fishes(20)
+            .sharks(4)
+            .extinction_policy(ExtinctionPolicy::StopOnAnyExtinct);
+        let table = Sweep::new(builder.clone()).steps(30).seed(3).run().unwrap();
+
+        let mut board = builder.seed(3).build().unwrap();
+        for _ in 0..30 {
+            if board.step().is_err() {
+                break;
+            }
+        }
+        assert_eq!(table.runs(), &[SweepRun::new(&board, 3)]);
+    }
+
+    #[test]
+    fn test_invalid_combination() {
+        let result = Sweep::new(Board::builder().dimensions(2, 2))
+            .fishes([1, 5])
+            .run();
+        assert!(matches!(result, Err(SimulationError::Overpopulated { .. })));
+    }
+
+    #[test]
+    fn test_densities() {
+        let table = Sweep::new(Board::builder().dimensions(10, 8).fishes(5).sharks(5))
+            .fish_densities([0.25, 0.5])
+            .shark_densities([0.1])
+            .steps(1)
+            .seed(1)
+            .run()
+            .unwrap();
+        let amounts: Vec<(u32, u32)> = table
+            .runs()
+            .iter()
+            .map(|run| (run.parameters.fishes, run.parameters.sharks))
+            .collect();
+        assert_eq!(amounts, vec![(20, 8), (40, 8)]);
+
+        // Amounts replace densities that were set before
+        let table = Sweep::new(Board::builder().dimensions(10, 8))
+            .fish_densities([0.25])
+            .fishes([3])
+            .steps(1)
+            .run()
+            .unwrap();
+        assert_eq!(table.runs()[0].parameters.fishes, 3);
+    }
+
+    #[test]
+    fn test_populations_with_layout() {
+        let builder = Board::builder().layout("F__\n_S_");
+        let result = Sweep::new(builder.clone()).fish_densities([0.5]).run();
+        assert!(
+            matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "fish_densities")
+        );
+        let result = Sweep::new(builder.clone()).sharks([1]).run();
+        assert!(
+            matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "sharks")
+        );
+
+        let table = Sweep::new(builder).fish_breed_times([2, 3]).run().unwrap();
+        assert_eq!(table.runs().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_density() {
+        for density in [-0.1, 1.5, f64::NAN] {
+            let result = Sweep::new(Board::builder().dimensions(10, 10))
+                .shark_densities([0.5, density])
+                .run();
+            assert!(
+                matches!(result, Err(SimulationError::InvalidConfig { key, .. }) if key == "shark_densities")
+            );
+        }
+    }
+
+    #[test]
+    fn test_oscillation_period() {
+        let wave: Vec<u32> = (0..200)
+            .map(|step| (100.0 + 50.0 * (step as f64 * std::f64::consts::TAU / 20.0).sin()) as u32)
+            .collect();
+        assert_eq!(oscillation_period(&wave), Some(20));
+        assert_eq!(oscillation_period(&[5; 50]), None);
+        assert_eq!(mean_and_variance(&[1, 3]), (2.0, 1.0));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let table = Sweep::new(Board::builder().dimensions(5, 5).fishes(5).sharks(2))
+            .repeats(2)
+            .steps(5)
+            .seed(1)
+            .run()
+            .unwrap();
+
+        let mut runs = vec![];
+        table.write_csv(&mut runs).unwrap();
+        let runs = String::from_utf8(runs).unwrap();
+        assert_eq!(runs.lines().count(), 3);
+        assert!(runs.starts_with(RUN_HEADER));
+
+        let mut summaries = vec![];
+        table.write_summary_csv(&mut summaries).unwrap();
+        let summaries = String::from_utf8(summaries).unwrap();
+        assert_eq!(summaries.lines().count(), 2);
+        assert!(summaries.lines().nth(1).unwrap().starts_with("3,8,5,2,2,"));
+    }
+}
@@ -0,0 +1,127 @@
+//! Contains the topologies that define how the edges of a board are connected.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, Topology};
+//!
+//! // A board surrounded by walls
+//! let mut board = Board::new(10, 5, 25, 25).with_topology(Topology::Box);
+//! board.generate_random_animals();
+//! ```
+use std::str::FromStr;
+
+use crate::{Result, SimulationError};
+
+type Position = (u32, u32);
+
+/// Describes which edges of the board wrap around
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// Both the left and right edge and the upper and lower edge are connected
+    #[default]
+    Torus,
+    /// All edges are walls
+    Box,
+    /// The left and right edge are connected, the upper and lower edge are walls
+    HorizontalCylinder,
+    /// The upper and lower edge are connected, the left and right edge are walls
+    VerticalCylinder,
+}
+
+impl Topology {
+    /// Returns `true` if the left and right edge of the board are connected
+    pub fn wraps_horizontally(&self) -> bool {
+        matches!(self, Topology::Torus | Topology::HorizontalCylinder)
+    }
+
+    /// Returns `true` if the upper and lower edge of the board are connected
+    pub fn wraps_vertically(&self) -> bool {
+        matches!(self, Topology::Torus | Topology::VerticalCylinder)
+    }
+
+    /// Moves a position by an offset
+    ///
+    /// # Arguments
+    /// * `position` - The position that is moved
+    /// * `offset` - The offset in columns and rows
+    /// * `rows` - The amount of rows of the board
+    /// * `columns` - The amount of columns of the board
+    ///
+    /// # Returns
+    /// The new position or `None` if the offset leads through a wall
+    pub fn offset(
+        &self,
+        position: Position,
+        offset: (i32, i32),
+        rows: u32,
+        columns: u32,
+    ) -> Option<Position> {
+        let x = Self::offset_coordinate(position.0, offset.0, columns, self.wraps_horizontally())?;
+        let y = Self::offset_coordinate(position.1, offset.1, rows, self.wraps_vertically())?;
+        Some((x, y))
+    }
+
+    fn offset_coordinate(coordinate: u32, offset: i32, size: u32, wraps: bool) -> Option<u32> {
+        let moved = i64::from(coordinate) + i64::from(offset);
+        if wraps {
+            return Some(moved.rem_euclid(i64::from(size)) as u32);
+        }
+
+        if (0..i64::from(size)).contains(&moved) {
+            Some(moved as u32)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = SimulationError;
+
+    /// Parses the snake case name of a topology, e.g. `horizontal_cylinder`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "torus" => Ok(Topology::Torus),
+            "box" => Ok(Topology::Box),
+            "horizontal_cylinder" => Ok(Topology::HorizontalCylinder),
+            "vertical_cylinder" => Ok(Topology::VerticalCylinder),
+            _ => Err(SimulationError::InvalidConfig {
+                key: "topology".into(),
+                message: format!(
+                    "unknown topology `{name}`, expected one of torus, box, horizontal_cylinder, \
+                     vertical_cylinder"
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_torus_wraps() {
+        assert_eq!(Topology::Torus.offset((0, 0), (-1, -1), 3, 5), Some((4, 2)));
+        assert_eq!(Topology::Torus.offset((4, 2), (1, 1), 3, 5), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_box_has_walls() {
+        assert_eq!(Topology::Box.offset((0, 1), (-1, 0), 3, 5), None);
+        assert_eq!(Topology::Box.offset((2, 2), (0, 1), 3, 5), None);
+        assert_eq!(Topology::Box.offset((2, 1), (1, 1), 3, 5), Some((3, 2)));
+    }
+
+    #[test]
+    fn test_cylinders() {
+        let horizontal = Topology::HorizontalCylinder;
+        assert_eq!(horizontal.offset((0, 1), (-1, 0), 3, 5), Some((4, 1)));
+        assert_eq!(horizontal.offset((0, 0), (0, -1), 3, 5), None);
+
+        let vertical = Topology::VerticalCylinder;
+        assert_eq!(vertical.offset((0, 1), (-1, 0), 3, 5), None);
+        assert_eq!(vertical.offset((0, 0), (0, -1), 3, 5), Some((0, 2)));
+    }
+}
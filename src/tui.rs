@@ -0,0 +1,283 @@
+//! Contains a full-screen terminal viewer for a board (requires the `tui` feature).
+//!
+//! The viewer redraws the board in place, shows the current amount of fishes and sharks and a
+//! sparkline of their history. It is controlled with the following keys:
+//!
+//! | Key               | Action                          |
+//! |-------------------|---------------------------------|
+//! | `Space`, `p`      | Pause or resume the simulation  |
+//! | `n`, `Right`      | Simulate a single step          |
+//! | `+`, `Up`         | Simulate faster                 |
+//! | `-`, `Down`       | Simulate slower                 |
+//! | `r`               | Restart with a new seed         |
+//! | `q`, `Esc`        | Quit                            |
+//!
+//! # Examples
+//! ```no_run
+//! use planetensimulation::{Board, Viewer};
+//!
+//! Viewer::new(|seed| {
+//!     Board::builder()
+//!         .dimensions(40, 40)
+//!         .fishes(200)
+//!         .sharks(100)
+//!         .seed(seed)
+//!         .build()
+//!         .unwrap()
+//! })
+//! .run()
+//! .unwrap();
+//! ```
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Sparkline},
+    DefaultTerminal, Frame,
+};
+
+use crate::{Board, FieldType};
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Interactive full-screen viewer for a simulation
+pub struct Viewer<F: FnMut(u64) -> Board> {
+    factory: F,
+    board: Board,
+    seed: u64,
+    delay: Duration,
+    paused: bool,
+    message: Option<String>,
+}
+
+impl<F: FnMut(u64) -> Board> Viewer<F> {
+    /// Creates a viewer
+    ///
+    /// # Arguments
+    /// * `factory` - Creates the board for a seed. It is called again with a new seed when the
+    ///   simulation is restarted.
+    pub fn new(mut factory: F) -> Self {
+        let seed = rand::random();
+        let board = factory(seed);
+        Viewer {
+            factory,
+            board,
+            seed,
+            delay: Duration::from_millis(200),
+            paused: false,
+            message: None,
+        }
+    }
+
+    /// Sets the time between two steps
+    ///
+    /// # Arguments
+    /// * `delay` - The initial delay, it can be changed with the keys while the viewer runs
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay.clamp(MIN_DELAY, MAX_DELAY);
+        self
+    }
+
+    /// Shows the viewer until it is quit
+    ///
+    /// # Errors
+    /// If the terminal cannot be set up or drawn to
+    pub fn run(mut self) -> io::Result<()> {
+        let mut terminal = ratatui::try_init()?;
+        let result = self.event_loop(&mut terminal);
+        ratatui::try_restore()?;
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut next_step = Instant::now() + self.delay;
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let timeout = next_step.saturating_duration_since(Instant::now());
+            if event::poll(timeout)? {
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') | KeyCode::Char('p') => self.paused = !self.paused,
+                    KeyCode::Char('n') | KeyCode::Right => {
+                        self.paused = true;
+                        self.step();
+                    }
+                    KeyCode::Char('+') | KeyCode::Up => {
+                        self.delay = (self.delay / 2).max(MIN_DELAY);
+                    }
+                    KeyCode::Char('-') | KeyCode::Down => {
+                        self.delay = (self.delay * 2).min(MAX_DELAY);
+                    }
+                    KeyCode::Char('r') => self.reseed(),
+                    _ => (),
+                }
+                continue;
+            }
+
+            if !self.paused {
+                self.step();
+            }
+            next_step = Instant::now() + self.delay;
+        }
+    }
+
+    /// Simulates a step and pauses if the board refuses to continue
+    fn step(&mut self) {
+        if let Err(error) = self.board.step() {
+            self.message = Some(error.to_string());
+            self.paused = true;
+        }
+    }
+
+    /// Restarts the simulation with a new board
+    fn reseed(&mut self) {
+        self.seed = rand::random();
+        self.board = (self.factory)(self.seed);
+        self.message = None;
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, grid, fishes, sharks, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let (amount_fishes, amount_sharks) = self.board.count_animals();
+        let state = match (&self.message, self.paused) {
+            (Some(message), _) => message.clone(),
+            (None, true) => "paused".into(),
+            (None, false) => "running".into(),
+        };
+        let status = Line::from(vec![
+            format!("Step {}  ", self.board.current_step()).bold(),
+            format!("Fishes {amount_fishes}  ").green(),
+            format!("Sharks {amount_sharks}  ").red(),
+            format!("Seed {}  ", self.seed).into(),
+            format!("Delay {} ms  ", self.delay.as_millis()).into(),
+            state.italic(),
+        ]);
+        frame.render_widget(Paragraph::new(status), header);
+
+        frame.render_widget(
+            Paragraph::new(self.grid_lines()).block(Block::bordered()),
+            grid,
+        );
+        self.draw_history(frame, fishes, "Fishes", Color::Green, |board| {
+            board.statistics().fishes().collect()
+        });
+        self.draw_history(frame, sharks, "Sharks", Color::Red, |board| {
+            board.statistics().sharks().collect()
+        });
+
+        let keys = "Space pause  n step  + faster  - slower  r reseed  q quit".dark_gray();
+        frame.render_widget(Paragraph::new(keys), footer);
+    }
+
+    fn grid_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::with_capacity(self.board.rows() as usize);
+        let mut spans = Vec::with_capacity(self.board.columns() as usize);
+        for field in self.board.cells() {
+            spans.push(match field.r#type {
+                FieldType::Fish => Span::styled("F ", Style::default().fg(Color::Green)),
+                FieldType::Shark => Span::styled("S ", Style::default().fg(Color::Red)),
+                FieldType::Plankton => Span::styled("_ ", Style::default().fg(Color::Blue)),
+            });
+            if field.x + 1 == self.board.columns() {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+        }
+        lines
+    }
+
+    fn draw_history(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        color: Color,
+        history: impl Fn(&Board) -> Vec<u32>,
+    ) {
+        let history = history(&self.board);
+        // Only the latest values fit into the area
+        let width = area.width.saturating_sub(2) as usize;
+        let data: Vec<u64> = history
+            .iter()
+            .skip(history.len().saturating_sub(width))
+            .map(|value| u64::from(*value))
+            .collect();
+        let sparkline = Sparkline::default()
+            .block(Block::bordered().title(title.to_string()))
+            .style(Style::default().fg(color))
+            .data(data);
+        frame.render_widget(sparkline, area);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render(viewer: &Viewer<impl FnMut(u64) -> Board>) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| viewer.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_draw_counts_and_grid() {
+        let mut viewer = Viewer::new(|seed| "FS_\n___".parse::<Board>().unwrap().with_seed(seed));
+        let screen = render(&viewer);
+        assert!(screen.contains("Step 0"));
+        assert!(screen.contains("Fishes 1"));
+        assert!(screen.contains("Sharks 1"));
+        assert!(screen.contains("F S _"));
+
+        viewer.step();
+        viewer.paused = true;
+        let screen = render(&viewer);
+        assert!(screen.contains("Step 1"));
+        assert!(screen.contains("paused"));
+    }
+
+    #[test]
+    fn test_reseed_restarts() {
+        let mut viewer = Viewer::new(|seed| {
+            Board::builder()
+                .dimensions(5, 5)
+                .fishes(5)
+                .sharks(2)
+                .seed(seed)
+                .build()
+                .unwrap()
+        });
+        viewer.step();
+        viewer.step();
+        viewer.reseed();
+        assert_eq!(viewer.board.current_step(), 0);
+    }
+}
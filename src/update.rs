@@ -0,0 +1,51 @@
+//! Contains the orders in which the animals of a board are updated during a step.
+//!
+//! # Examples
+//! ```
+//! use planetensimulation::{Board, UpdateOrder};
+//!
+//! // All animals decide based on the state before the step
+//! let mut board = Board::new(10, 5, 25, 25).with_update_order(UpdateOrder::Synchronous);
+//! board.generate_random_animals();
+//! board.step().unwrap();
+//! ```
+use std::str::FromStr;
+
+use crate::{Result, SimulationError};
+
+/// Describes in which order the animals act during a step
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateOrder {
+    /// First all fishes and then all sharks act one after another in row-major order. Every
+    /// animal sees the moves of the animals that acted before it.
+    #[default]
+    Sequential,
+    /// Like [`UpdateOrder::Sequential`] but the order of the fishes and of the sharks is
+    /// shuffled in every step
+    Shuffled,
+    /// All animals decide based on the state before the step. If several animals want to move
+    /// to the same field, a random one of them moves and the others stay where they are. If
+    /// several sharks want to eat the same fish, a random one of them eats it and the others
+    /// stay hungry.
+    Synchronous,
+}
+
+impl FromStr for UpdateOrder {
+    type Err = SimulationError;
+
+    /// Parses the snake case name of an update order, e.g. `synchronous`
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "sequential" => Ok(UpdateOrder::Sequential),
+            "shuffled" => Ok(UpdateOrder::Shuffled),
+            "synchronous" => Ok(UpdateOrder::Synchronous),
+            _ => Err(SimulationError::InvalidConfig {
+                key: "update_order".into(),
+                message: format!(
+                    "unknown update order `{name}`, expected one of sequential, shuffled, synchronous"
+                ),
+            }),
+        }
+    }
+}